@@ -0,0 +1,280 @@
+// Plays many headless games with a bot and prints statistics, e.g.
+// `snake-sim --games 1000 --seed 1 --bot Greedy --format csv`.
+// Difficulty only sets the time between ticks, so it has no option: results are per tick.
+// Used for balancing `assets/powerups.ron` and catching rule regressions.
+
+use std::{collections::BTreeMap, fmt::Write, fs, process::ExitCode, time::Instant};
+
+use serde::{de::DeserializeOwned, Serialize, Serializer};
+use snake::libs::{
+    death::DeathCause,
+    eatables::powerups::registry::PowerupRegistry,
+    game_configuration::{BoundaryMode, GameConfiguration, GridPreset},
+    game_rng::GameRng,
+    level::{level_name, Level},
+    sim::{
+        bot::{choose_direction, BotLevel, BotView},
+        sim_event::SimEvent,
+        snake_sim::SnakeSim,
+    },
+    utils::cli_arg,
+};
+
+const DEFAULT_GAMES: u64 = 100;
+// Games still going after this many ticks are stopped, a bot may circle forever.
+const DEFAULT_MAX_TICKS: u64 = 10_000;
+const DEFAULT_POWERUPS: &str = "assets/powerups.ron";
+
+#[derive(Serialize)]
+struct GameResult {
+    seed: u64,
+    score: u32,
+    length: usize,
+    ticks: u64,
+    // `None` when the game hit the tick limit.
+    #[serde(serialize_with = "serialize_death")]
+    death: Option<DeathCause>,
+}
+
+fn death_name(death: Option<DeathCause>) -> String {
+    death.map_or("TimedOut".to_string(), |death| format!("{death:?}"))
+}
+
+fn serialize_death<S: Serializer>(
+    death: &Option<DeathCause>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&death_name(*death))
+}
+
+#[derive(Serialize)]
+struct ScoreStats {
+    min: u32,
+    median: u32,
+    p90: u32,
+    max: u32,
+    mean: f64,
+}
+
+// Statistics over every game, printed with both formats.
+#[derive(Serialize)]
+struct Summary {
+    games: usize,
+    bot: BotLevel,
+    ticks_per_second: u64,
+    score: ScoreStats,
+    average_length: f64,
+    death_causes: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    fn new(results: &[GameResult], bot: BotLevel, ticks_per_second: f64) -> Self {
+        let mut scores: Vec<u32> = results.iter().map(|result| result.score).collect();
+        scores.sort_unstable();
+
+        let games = results.len() as f64;
+        let mut death_causes = BTreeMap::new();
+
+        for result in results {
+            *death_causes.entry(death_name(result.death)).or_default() += 1;
+        }
+
+        Self {
+            games: results.len(),
+            bot,
+            ticks_per_second: ticks_per_second.round() as u64,
+            score: ScoreStats {
+                min: scores[0],
+                median: percentile(&scores, 50),
+                p90: percentile(&scores, 90),
+                max: scores[scores.len() - 1],
+                mean: scores.iter().sum::<u32>() as f64 / games,
+            },
+            average_length: results.iter().map(|result| result.length).sum::<usize>() as f64
+                / games,
+            death_causes,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    #[serde(flatten)]
+    summary: Summary,
+    results: &'a [GameResult],
+}
+
+// Options are given by their variant name, e.g. `--bot Pathfinding`.
+fn parse_arg<T: DeserializeOwned>(name: &str) -> Result<Option<T>, String> {
+    cli_arg(name)
+        .map(|value| ron::from_str(&value).map_err(|_| format!("invalid value {value} for {name}")))
+        .transpose()
+}
+
+fn parse_number(name: &str, default: u64) -> Result<u64, String> {
+    cli_arg(name).map_or(Ok(default), |value| {
+        value
+            .parse()
+            .map_err(|_| format!("invalid number {value} for {name}"))
+    })
+}
+
+fn configuration_from_args() -> Result<GameConfiguration, String> {
+    let mut configuration = GameConfiguration::default();
+
+    if cli_arg("--difficulty").is_some() {
+        return Err("--difficulty has no effect, games are measured in ticks".to_string());
+    }
+
+    if let Some(preset) = parse_arg::<GridPreset>("--grid")? {
+        configuration.set_grid_preset(preset);
+    }
+
+    if let Some(boundary_mode) = parse_arg::<BoundaryMode>("--boundary")? {
+        configuration.boundary_mode = boundary_mode;
+    }
+
+    if let Some(path) = cli_arg("--level") {
+        let text = fs::read_to_string(&path).map_err(|err| format!("can't read {path}: {err}"))?;
+        let level = Level::parse(level_name(&path), &text).map_err(|err| err.to_string())?;
+        configuration.set_level(level);
+    }
+
+    Ok(configuration)
+}
+
+fn play(
+    configuration: &GameConfiguration,
+    registry: &PowerupRegistry,
+    bot: BotLevel,
+    seed: u64,
+    max_ticks: u64,
+) -> GameResult {
+    let mut sim = SnakeSim::new(configuration, registry, seed);
+    let mut rng = GameRng::new(seed.wrapping_add(1));
+    let mut death = None;
+
+    while !sim.game_over && sim.tick < max_ticks {
+        let view = BotView::from_sim(&sim);
+        let direction = choose_direction(bot, &view, sim.head(), sim.direction, &mut rng);
+
+        for event in sim.step(Some(direction)) {
+            if let SimEvent::Died(cause) = event {
+                death = Some(cause);
+            }
+        }
+    }
+
+    GameResult {
+        seed,
+        score: sim.score,
+        length: sim.body.len(),
+        ticks: sim.tick,
+        death,
+    }
+}
+
+fn percentile(sorted: &[u32], percent: usize) -> u32 {
+    sorted[(sorted.len() - 1) * percent / 100]
+}
+
+// One row per game, then the summary as `stat,value` rows after an empty line.
+fn to_csv(results: &[GameResult], summary: &Summary) -> String {
+    let mut csv = "seed,score,length,ticks,death\n".to_string();
+
+    for result in results {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            result.seed,
+            result.score,
+            result.length,
+            result.ticks,
+            death_name(result.death)
+        );
+    }
+
+    let score = &summary.score;
+    let mut stats = vec![
+        ("games".to_string(), summary.games.to_string()),
+        ("bot".to_string(), format!("{:?}", summary.bot)),
+        (
+            "ticks_per_second".to_string(),
+            summary.ticks_per_second.to_string(),
+        ),
+        ("score_min".to_string(), score.min.to_string()),
+        ("score_median".to_string(), score.median.to_string()),
+        ("score_p90".to_string(), score.p90.to_string()),
+        ("score_max".to_string(), score.max.to_string()),
+        ("score_mean".to_string(), format!("{:.2}", score.mean)),
+        (
+            "average_length".to_string(),
+            format!("{:.2}", summary.average_length),
+        ),
+    ];
+
+    for (death, count) in summary.death_causes.iter() {
+        stats.push((format!("death_{death}"), count.to_string()));
+    }
+
+    csv.push_str("\nstat,value\n");
+
+    for (stat, value) in stats {
+        let _ = writeln!(csv, "{stat},{value}");
+    }
+
+    csv
+}
+
+fn to_json(results: &[GameResult], summary: Summary) -> Result<String, String> {
+    serde_json::to_string_pretty(&Report { summary, results }).map_err(|err| err.to_string())
+}
+
+fn run() -> Result<String, String> {
+    let configuration = configuration_from_args()?;
+    let bot = parse_arg::<BotLevel>("--bot")?.unwrap_or_default();
+    let games = parse_number("--games", DEFAULT_GAMES)?.max(1);
+    let first_seed = parse_number("--seed", 0)?;
+    let last_seed = first_seed.checked_add(games - 1).ok_or(format!(
+        "--seed {first_seed} with {games} games runs past the last seed"
+    ))?;
+    let max_ticks = parse_number("--max-ticks", DEFAULT_MAX_TICKS)?;
+
+    let powerups = cli_arg("--powerups").unwrap_or(DEFAULT_POWERUPS.to_string());
+    let registry = match fs::read_to_string(&powerups) {
+        Ok(text) => PowerupRegistry::from_ron(&text).map_err(|err| format!("{powerups}: {err}"))?,
+        Err(err) => {
+            eprintln!("Can't read {powerups} ({err}), using the built-in powerups");
+            PowerupRegistry::default()
+        }
+    };
+
+    let started = Instant::now();
+    let results: Vec<GameResult> = (first_seed..=last_seed)
+        .map(|seed| play(&configuration, &registry, bot, seed, max_ticks))
+        .collect();
+
+    let ticks: u64 = results.iter().map(|result| result.ticks).sum();
+    let ticks_per_second = ticks as f64 / started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+    let summary = Summary::new(&results, bot, ticks_per_second);
+
+    match cli_arg("--format").as_deref() {
+        Some("csv") => Ok(to_csv(&results, &summary)),
+        None | Some("json") => to_json(&results, summary),
+        Some(format) => Err(format!("unknown format {format}, expected json or csv")),
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(output) => {
+            println!("{}", output.trim_end());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("snake-sim: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
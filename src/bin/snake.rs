@@ -11,19 +11,31 @@ use bevy::{
 use bevy_particle_systems::ParticleSystemPlugin;
 use snake::libs::{
     animation::CustomAnimationPlugin,
+    attract::AttractModePlugin,
     audio::AudioPlugin,
+    board::BoardPlugin,
+    bots::BotsPlugin,
     camera::CameraPlugin,
     cell::CellPlugin,
+    death::DeathPlugin,
     eatables::{eat_event::EatEventPlugin, food::FoodPlugin, powerups::powerup::PowerupPlugins},
-    game_configuration::GameConfigurationPlugin,
+    game_configuration::{GameConfiguration, GameConfigurationPlugin},
+    game_rng::GameRngPlugin,
     game_states::GameStatatesPlugin,
-    globals::{BACKGROUND_COLOR, WINDOW_SIZE},
-    input::{action_events::ActionEventsPlugin, read_input::ReadInputPlugin},
+    globals::BACKGROUND_COLOR,
+    high_scores::HighScoresPlugin,
+    input::{
+        action_events::ActionEventsPlugin, bindings::InputBindingsPlugin,
+        devices::InputDevicesPlugin, read_input::ReadInputPlugin,
+    },
+    level::LevelPlugin,
     particles::OnEatParticlePlugin,
     pause::GamePausePlugin,
+    replay::replay_plugin::ReplayPlugins,
     schedule::GameSchedulePlugin,
     score::ScorePlugin,
     snake::SnakePlugin,
+    snake_body::SnakeBodyPlugin,
     ui::snake_ui_plugin::SnakeUiPlugins,
 };
 use winit::window::Icon;
@@ -49,13 +61,16 @@ fn set_window_icon(
 }
 
 fn main() {
+    // The window follows the grid size, see `GameConfiguration::window_size`.
+    let window_size = GameConfiguration::default().window_size();
+
     let mut app = App::new();
     app.insert_resource(ClearColor(BACKGROUND_COLOR))
         .insert_resource(AssetMetaCheck::Never)
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Snake".into(),
-                resolution: (WINDOW_SIZE, WINDOW_SIZE).into(),
+                resolution: (window_size.x, window_size.y).into(),
                 enabled_buttons: bevy::window::EnabledButtons {
                     maximize: false,
                     ..Default::default()
@@ -73,17 +88,28 @@ fn main() {
         .add_plugins(AudioPlugin)
         .add_plugins(ParticleSystemPlugin)
         .add_plugins(GameConfigurationPlugin)
+        .add_plugins(GameRngPlugin)
         .add_plugins(CustomAnimationPlugin)
         .add_plugins(CameraPlugin)
+        .add_plugins(InputBindingsPlugin)
+        .add_plugins(InputDevicesPlugin)
         .add_plugins(ReadInputPlugin)
         .add_plugins(ActionEventsPlugin)
         .add_plugins(ScorePlugin)
+        .add_plugins(HighScoresPlugin)
         .add_plugins(CellPlugin)
+        .add_plugins(LevelPlugin)
+        .add_plugins(BoardPlugin)
+        .add_plugins(DeathPlugin)
+        .add_plugins(BotsPlugin)
+        .add_plugins(AttractModePlugin)
         .add_plugins(FoodPlugin)
         .add_plugins(SnakePlugin)
+        .add_plugins(SnakeBodyPlugin)
         .add_plugins(OnEatParticlePlugin)
         .add_plugins(GamePausePlugin)
         .add_plugins(PowerupPlugins)
+        .add_plugins(ReplayPlugins)
         .add_plugins(SnakeUiPlugins);
 
     app.run();
@@ -1,7 +1,5 @@
 use bevy::prelude::*;
 
-use super::globals::GRID_CELL;
-
 pub const BREATHE_ANIMATION_NAME: &str = "breathe";
 
 #[derive(Resource, Default, Clone)]
@@ -23,11 +21,7 @@ fn create_animations(
         },
         VariableCurve {
             keyframe_timestamps: vec![0.0, 1.0, 1.5],
-            keyframes: Keyframes::Scale(vec![
-                Vec3::splat(GRID_CELL * 1.0),
-                Vec3::splat(GRID_CELL * 0.8),
-                Vec3::splat(GRID_CELL * 1.0),
-            ]),
+            keyframes: Keyframes::Scale(vec![Vec3::splat(1.0), Vec3::splat(0.8), Vec3::splat(1.0)]),
         },
     );
 
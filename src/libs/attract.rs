@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    eatables::powerups::registry::PowerupRegistry,
+    game_configuration::GameConfiguration,
+    game_rng::GameRng,
+    game_states::GameState,
+    globals::{HEAD_COLOR, OBSTACLE_COLOR, TAIL_COLOR},
+    sim::{
+        bot::{choose_direction, BotLevel, BotView},
+        snake_sim::SnakeSim,
+    },
+    utils::grid_to_screen,
+};
+
+// Demo sprites are faded, so the menu stays readable on top of them.
+const ATTRACT_ALPHA: f32 = 0.4;
+// A bot that can't reach any food may circle forever, the demo starts over after this.
+const ATTRACT_MAX_TICKS: u64 = 2000;
+
+// Bot-driven game shown behind the start menu. It runs on the headless sim,
+// so the real game's entities, score and replays are never touched.
+#[derive(Resource)]
+struct AttractGame {
+    sim: SnakeSim,
+    timer: Timer,
+    rng: GameRng,
+    // Set when the board has to be drawn again.
+    dirty: bool,
+}
+
+impl AttractGame {
+    fn new(configuration: &GameConfiguration, registry: &PowerupRegistry) -> Self {
+        let sim = SnakeSim::new(configuration, registry, rand::random());
+
+        Self {
+            timer: Timer::from_seconds(sim.tick_rate(), TimerMode::Repeating),
+            rng: GameRng::new(sim.seed()),
+            sim,
+            dirty: true,
+        }
+    }
+
+    fn matches(&self, configuration: &GameConfiguration) -> bool {
+        self.sim.width == configuration.grid_width
+            && self.sim.height == configuration.grid_height
+            && self.sim.boundary_mode == configuration.boundary_mode
+            && self.sim.walls.as_slice() == configuration.walls()
+    }
+
+    fn step(&mut self) {
+        let direction = choose_direction(
+            BotLevel::Pathfinding,
+            &BotView::from_sim(&self.sim),
+            self.sim.head(),
+            self.sim.direction,
+            &mut self.rng,
+        );
+
+        self.sim.step(Some(direction));
+        self.timer
+            .set_duration(Duration::from_secs_f32(self.sim.tick_rate()));
+        self.dirty = true;
+    }
+}
+
+#[derive(Component)]
+struct AttractSprite;
+
+fn start_attract(
+    mut commands: Commands,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
+) {
+    commands.insert_resource(AttractGame::new(&configuration, &registry));
+}
+
+fn run_attract(
+    mut game: ResMut<AttractGame>,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
+    time: Res<Time>,
+) {
+    // Board options picked in the menu show up right away,
+    // and a dead bot starts over one tick after it died.
+    if !game.matches(&configuration) {
+        *game = AttractGame::new(&configuration, &registry);
+        return;
+    }
+
+    if !game.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if game.sim.game_over || game.sim.tick >= ATTRACT_MAX_TICKS {
+        *game = AttractGame::new(&configuration, &registry);
+    } else {
+        game.step();
+    }
+}
+
+fn render_attract(
+    mut commands: Commands,
+    mut game: ResMut<AttractGame>,
+    sprites: Query<Entity, With<AttractSprite>>,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
+) {
+    if !game.dirty {
+        return;
+    }
+
+    game.dirty = false;
+
+    for sprite in sprites.iter() {
+        commands.entity(sprite).despawn();
+    }
+
+    let sim = &game.sim;
+    let walls = sim.walls.iter().map(|wall| (*wall, OBSTACLE_COLOR));
+    let foods = sim
+        .foods
+        .iter()
+        .map(|food| (food.pos, registry.entry(&food.id).get_color()));
+    let body = sim.body.iter().enumerate().map(|(id, part)| {
+        let color = if id == 0 { HEAD_COLOR } else { TAIL_COLOR };
+        (*part, color)
+    });
+
+    for (cell, color) in walls.chain(foods).chain(body) {
+        let pos = grid_to_screen(cell.x, cell.y, &configuration);
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: color.with_a(ATTRACT_ALPHA),
+                    custom_size: Some(Vec2::splat(configuration.cell_size)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(pos.x, pos.y, 0.),
+                ..default()
+            },
+            AttractSprite,
+        ));
+    }
+}
+
+fn stop_attract(mut commands: Commands, sprites: Query<Entity, With<AttractSprite>>) {
+    for sprite in sprites.iter() {
+        commands.entity(sprite).despawn();
+    }
+
+    commands.remove_resource::<AttractGame>();
+}
+
+pub struct AttractModePlugin;
+
+impl Plugin for AttractModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::StartMenu), start_attract)
+            .add_systems(
+                Update,
+                (run_attract, render_attract)
+                    .chain()
+                    .run_if(in_state(GameState::StartMenu))
+                    .run_if(resource_exists::<AttractGame>()),
+            )
+            .add_systems(OnExit(GameState::StartMenu), stop_attract);
+    }
+}
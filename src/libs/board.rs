@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use super::{
+    cell::{Cell, CellBundle},
+    death::{DeathCause, DeathEvent},
+    game_configuration::{BoundaryMode, GameConfiguration},
+    game_states::GameState,
+    globals::{BORDER_COLOR, OBSTACLE_COLOR},
+    player::PlayerId,
+    schedule::InGameSet,
+    snake::Head,
+};
+
+const BORDER_THICKNESS: f32 = 4.0;
+
+#[derive(Component)]
+struct BoardBorder;
+
+// Level wall, the snake dies when its head runs into one.
+#[derive(Component, Reflect)]
+pub struct Obstacle;
+
+fn spawn_obstacles(mut commands: Commands, configuration: Res<GameConfiguration>) {
+    for wall in configuration.walls() {
+        commands.spawn((
+            CellBundle::new(*wall, OBSTACLE_COLOR, &configuration),
+            Obstacle,
+        ));
+    }
+}
+
+fn despawn_obstacles(mut commands: Commands, query: Query<Entity, With<Obstacle>>) {
+    for obstacle in query.iter() {
+        commands.entity(obstacle).despawn();
+    }
+}
+
+fn obstacle_collision(
+    heads: Query<(&Cell, &PlayerId), With<Head>>,
+    obstacles: Query<&Cell, With<Obstacle>>,
+    mut ev_death: EventWriter<DeathEvent>,
+) {
+    for (head, player) in heads.iter() {
+        if obstacles.iter().any(|obstacle| obstacle == head) {
+            ev_death.send(DeathEvent {
+                player: *player,
+                cause: DeathCause::Wall,
+            });
+        }
+    }
+}
+
+fn spawn_border(mut commands: Commands, configuration: Res<GameConfiguration>) {
+    if configuration.boundary_mode != BoundaryMode::Solid {
+        return;
+    }
+
+    let size = configuration.window_size();
+    let half = size / 2.;
+
+    // Top, bottom, left and right edges, drawn inside the window over the outer cells.
+    let edges = [
+        (
+            Vec2::new(0., half.y - BORDER_THICKNESS / 2.),
+            Vec2::new(size.x, BORDER_THICKNESS),
+        ),
+        (
+            Vec2::new(0., -half.y + BORDER_THICKNESS / 2.),
+            Vec2::new(size.x, BORDER_THICKNESS),
+        ),
+        (
+            Vec2::new(-half.x + BORDER_THICKNESS / 2., 0.),
+            Vec2::new(BORDER_THICKNESS, size.y),
+        ),
+        (
+            Vec2::new(half.x - BORDER_THICKNESS / 2., 0.),
+            Vec2::new(BORDER_THICKNESS, size.y),
+        ),
+    ];
+
+    for (pos, edge_size) in edges {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BORDER_COLOR,
+                    custom_size: Some(edge_size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(pos.x, pos.y, 3.),
+                ..default()
+            },
+            BoardBorder,
+        ));
+    }
+}
+
+fn despawn_border(mut commands: Commands, query: Query<Entity, With<BoardBorder>>) {
+    for border in query.iter() {
+        commands.entity(border).despawn();
+    }
+}
+
+pub struct BoardPlugin;
+
+impl Plugin for BoardPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Obstacle>()
+            .add_systems(OnEnter(GameState::InGame), (spawn_border, spawn_obstacles))
+            .add_systems(
+                Update,
+                obstacle_collision.in_set(InGameSet::CollisionDetection),
+            )
+            .add_systems(
+                OnExit(GameState::InGame),
+                (despawn_border, despawn_obstacles),
+            );
+    }
+}
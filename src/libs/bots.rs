@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+
+use super::{
+    cell::Cell,
+    death::DeathEvent,
+    eatables::food::Food,
+    game_configuration::GameConfiguration,
+    game_rng::{reseed_game_rng, GameRng},
+    game_states::GameState,
+    input::action_events::ActionMoveEvent,
+    player::PlayerId,
+    replay::playback::ReplayPlayback,
+    schedule::InGameSet,
+    sim::bot::{choose_direction, BotView},
+    snake::{Head, Snake, Tail},
+};
+
+// Head of a snake steered by the computer.
+#[derive(Component, Reflect)]
+pub struct Bot;
+
+// Bots roll their own dice, so they don't change where food spawns.
+#[derive(Resource, Default)]
+struct BotRng(GameRng);
+
+fn reseed_bot_rng(mut bot_rng: ResMut<BotRng>, rng: Res<GameRng>) {
+    bot_rng.0 = GameRng::new(rng.seed().wrapping_add(1));
+}
+
+// Bots decide right before the snakes move and send the same moves a player would.
+fn drive_bots(
+    configuration: Res<GameConfiguration>,
+    bots: Query<(&Cell, &Head, &PlayerId), With<Bot>>,
+    parts: Query<&Cell, Or<(With<Head>, With<Tail>)>>,
+    foods: Query<&Cell, With<Food>>,
+    mut rng: ResMut<BotRng>,
+    mut ev_action_move: EventWriter<ActionMoveEvent>,
+) {
+    if !configuration.tick_timer.just_finished() || bots.is_empty() {
+        return;
+    }
+
+    let mut view = BotView::new(
+        configuration.grid_width,
+        configuration.grid_height,
+        configuration.boundary_mode,
+    );
+    view.block(configuration.walls().iter().copied());
+    view.block(parts.iter().copied());
+    view.foods = foods.iter().copied().collect();
+
+    for (cell, head, player) in bots.iter() {
+        let direction = choose_direction(
+            configuration.bot_level,
+            &view,
+            *cell,
+            head.direction,
+            &mut rng.0,
+        );
+
+        if direction != head.direction {
+            ev_action_move.send(ActionMoveEvent {
+                player: *player,
+                direction,
+            });
+        }
+    }
+}
+
+// A dead bot leaves the board, the game goes on while players are alive.
+fn remove_dead_bots(
+    mut commands: Commands,
+    mut ev_death: EventReader<DeathEvent>,
+    bots: Query<(&Snake, &PlayerId), With<Bot>>,
+) {
+    let dead: Vec<PlayerId> = ev_death.read().map(|death| death.player).collect();
+
+    for (snake, player) in bots.iter() {
+        if dead.contains(player) {
+            for part in snake.parts.iter() {
+                commands.entity(*part).despawn_recursive();
+            }
+        }
+    }
+}
+
+pub struct BotsPlugin;
+
+impl Plugin for BotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Bot>()
+            .insert_resource(BotRng::default())
+            .add_systems(
+                OnEnter(GameState::InGame),
+                reseed_bot_rng.after(reseed_game_rng),
+            )
+            // Replays already hold every bot move.
+            .add_systems(
+                Update,
+                drive_bots
+                    .in_set(InGameSet::UserInput)
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_systems(
+                Update,
+                remove_dead_bots
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
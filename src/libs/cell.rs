@@ -1,45 +1,101 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::{
-    eatables::food::Food, game_configuration::GameConfiguration, globals::GRID_CELL,
-    schedule::InGameSet, utils::grid_to_screen,
+    eatables::food::Food, game_configuration::GameConfiguration, schedule::InGameSet,
+    utils::grid_to_screen,
 };
 
-#[derive(Component, Clone, Copy, Default, PartialEq, Reflect)]
+#[derive(Component, Clone, Copy, Default, PartialEq, Reflect, Debug, Serialize, Deserialize)]
 pub struct Cell {
     pub x: u32,
     pub y: u32,
 }
 
+// The cell an entity moved from and to on the last tick, used for smooth movement.
+#[derive(Component, Clone, Copy)]
+pub struct CellMotion {
+    pub from: Cell,
+    pub to: Cell,
+}
+
+impl CellMotion {
+    fn new(cell: Cell) -> Self {
+        Self {
+            from: cell,
+            to: cell,
+        }
+    }
+
+    // Screen position `progress` (0 to 1) of the way through the move.
+    fn screen_position(&self, progress: f32, configuration: &GameConfiguration) -> Vec2 {
+        let to = grid_to_screen(self.to.x, self.to.y, configuration);
+
+        let (Some(x), Some(y)) = (
+            grid_step(self.from.x, self.to.x, configuration.grid_width),
+            grid_step(self.from.y, self.to.y, configuration.grid_height),
+        ) else {
+            return to;
+        };
+
+        // Grid y grows downwards, screen y upwards.
+        let step = Vec2::new(x, -y) * configuration.cell_size;
+
+        // Over a wrapping edge the sprite leaves one side in the first half of the tick
+        // and comes in on the other side in the second half, instead of crossing the board.
+        if progress < 0.5 {
+            grid_to_screen(self.from.x, self.from.y, configuration) + step * progress
+        } else {
+            to - step * (1. - progress)
+        }
+    }
+}
+
+// Signed step between two coordinates of neighbouring cells, counting wrapping around
+// the board as one step. `None` when they aren't neighbours, e.g. a respawned entity.
+fn grid_step(from: u32, to: u32, size: u32) -> Option<f32> {
+    let delta = to as i64 - from as i64;
+    let size = size as i64;
+
+    match delta {
+        -1..=1 => Some(delta as f32),
+        _ if delta == size - 1 => Some(-1.),
+        _ if delta == 1 - size => Some(1.),
+        _ => None,
+    }
+}
+
 #[derive(Bundle)]
 pub struct CellBundle {
     pub cell: Cell,
+    pub motion: CellMotion,
     pub sprite: SpriteBundle,
 }
 
 impl CellBundle {
-    pub fn new(cell: Cell, color: Color) -> Self {
-        Self::new_with_z(cell, color, 0.)
+    pub fn new(cell: Cell, color: Color, configuration: &GameConfiguration) -> Self {
+        Self::new_with_z(cell, color, 0., configuration)
     }
 
-    pub fn new_with_z(cell: Cell, color: Color, z: f32) -> Self {
-        let pos = grid_to_screen(cell.x, cell.y);
+    pub fn new_with_z(cell: Cell, color: Color, z: f32, configuration: &GameConfiguration) -> Self {
+        let pos = grid_to_screen(cell.x, cell.y, configuration);
 
         Self {
             cell,
+            motion: CellMotion::new(cell),
             sprite: SpriteBundle {
-                sprite: Sprite { color, ..default() },
+                // Size is set on the sprite, so the transform scale stays free for animations.
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(configuration.cell_size)),
+                    ..default()
+                },
                 transform: Transform {
                     translation: Vec3 {
                         x: pos.x,
                         y: pos.y,
                         z,
                     },
-                    scale: Vec3 {
-                        x: GRID_CELL,
-                        y: GRID_CELL,
-                        ..default()
-                    },
                     ..default()
                 },
                 ..default()
@@ -49,19 +105,35 @@ impl CellBundle {
 }
 
 fn update_cells_positions(
-    mut query: Query<(&Cell, &mut Transform), Without<Food>>,
+    mut query: Query<(&Cell, &mut CellMotion, &mut Transform), Without<Food>>,
     game_configration: Res<GameConfiguration>,
 ) {
-    if game_configration.tick_timer.just_finished() {
-        for (cell, mut transform) in query.iter_mut() {
-            let new_pos = grid_to_screen(cell.x, cell.y);
-
-            transform.translation = Vec3 {
-                x: new_pos.x,
-                y: new_pos.y,
-                z: transform.translation.z,
+    let tick_finished = game_configration.tick_timer.just_finished();
+
+    // Snapping only has something to do when the cells moved.
+    if !tick_finished && !game_configration.smooth_movement {
+        return;
+    }
+
+    for (cell, mut motion, mut transform) in query.iter_mut() {
+        if tick_finished {
+            *motion = CellMotion {
+                from: motion.to,
+                to: *cell,
             };
         }
+
+        let new_pos = if game_configration.smooth_movement {
+            motion.screen_position(game_configration.tick_timer.percent(), &game_configration)
+        } else {
+            grid_to_screen(cell.x, cell.y, &game_configration)
+        };
+
+        transform.translation = Vec3 {
+            x: new_pos.x,
+            y: new_pos.y,
+            z: transform.translation.z,
+        };
     }
 }
 
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use super::{
+    game_configuration::{GameConfiguration, GameMode},
+    game_states::GameState,
+    player::PlayerId,
+    schedule::InGameSet,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeathCause {
+    #[default]
+    SelfCollision,
+    Wall,
+    // Ran into the body of another snake.
+    SnakeCollision,
+    // Both heads met on the same cell.
+    HeadOn,
+}
+
+impl DeathCause {
+    pub fn get_message(&self) -> &'static str {
+        match self {
+            DeathCause::SelfCollision => "You bit your tail",
+            DeathCause::Wall => "You hit the wall",
+            DeathCause::SnakeCollision => "You ran into the other snake",
+            DeathCause::HeadOn => "Head-on collision",
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct DeathEvent {
+    pub player: PlayerId,
+    pub cause: DeathCause,
+}
+
+// Why the last game ended, shown on the finish menu.
+#[derive(Resource, Default)]
+pub struct LastDeathCause(pub DeathCause);
+
+// Snake left alive when a versus round ended, `None` for a draw.
+#[derive(Resource, Default)]
+pub struct RoundWinner(pub Option<PlayerId>);
+
+fn finish_game_on_death(
+    mut ev_death: EventReader<DeathEvent>,
+    mut last_death_cause: ResMut<LastDeathCause>,
+    mut winner: ResMut<RoundWinner>,
+    configuration: Res<GameConfiguration>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Bots dying doesn't end the game.
+    let deaths: Vec<&DeathEvent> = ev_death
+        .read()
+        .filter(|death| !configuration.is_bot(death.player))
+        .collect();
+
+    // Several causes can happen on the same tick, the first one sent wins.
+    let Some(first) = deaths.first() else {
+        return;
+    };
+
+    last_death_cause.0 = first.cause;
+
+    // Snakes dying on the same tick make a draw.
+    let mut survivors = PlayerId::all(configuration.mode.player_count())
+        .filter(|player| deaths.iter().all(|death| death.player != *player));
+
+    winner.0 = match configuration.mode {
+        GameMode::Single => None,
+        GameMode::Versus => survivors.next(),
+    };
+
+    next_state.set(GameState::FinishMenu);
+}
+
+pub struct DeathPlugin;
+
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DeathEvent>()
+            .insert_resource(LastDeathCause::default())
+            .insert_resource(RoundWinner::default())
+            .add_systems(
+                Update,
+                finish_game_on_death
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
@@ -1,25 +1,31 @@
 use bevy::prelude::*;
 
-use crate::libs::{cell::Cell, schedule::InGameSet, snake::Head};
+use crate::libs::{cell::Cell, player::PlayerId, schedule::InGameSet, snake::Head};
 
 use super::food::Food;
 
 #[derive(Event)]
 pub struct EatEvent {
+    pub player: PlayerId,
     pub id: Entity,
     pub pos: Cell,
     pub food: Food,
 }
 
-fn send_eat_event(
-    head: Query<&Cell, With<Head>>,
+pub fn send_eat_event(
+    heads: Query<(&Cell, &PlayerId), With<Head>>,
     foods: Query<(Entity, &Cell, &Food)>,
     mut ev_eat: EventWriter<EatEvent>,
 ) {
-    if let Ok(&head) = head.get_single() {
-        for (id, &pos, &food) in foods.iter() {
+    for (&head, &player) in heads.iter() {
+        for (id, &pos, food) in foods.iter() {
             if head == pos {
-                ev_eat.send(EatEvent { id, pos, food });
+                ev_eat.send(EatEvent {
+                    player,
+                    id,
+                    pos,
+                    food: food.clone(),
+                });
             }
         }
     }
@@ -1,18 +1,26 @@
 use bevy::prelude::*;
-use rand::Rng;
 
 use crate::libs::{
     animation::{AnimationHandles, BREATHE_ANIMATION_NAME},
     cell::{Cell, CellBundle},
     game_configuration::GameConfiguration,
+    game_rng::{reseed_game_rng, GameRng},
     game_states::GameState,
     schedule::InGameSet,
+    sim::rules,
 };
 
-use super::{eat_event::EatEvent, powerups::powerup::Powerup};
+use super::{
+    eat_event::{send_eat_event, EatEvent},
+    powerups::{
+        powerup::Powerup,
+        registry::{PowerupEntry, PowerupRegistry},
+    },
+};
 
-#[derive(Component, Clone, Copy)]
-pub struct Food(pub Powerup);
+// Id of the registry entry the food was spawned from.
+#[derive(Component, Clone)]
+pub struct Food(pub String);
 
 #[derive(Bundle)]
 pub struct FoodBundle {
@@ -27,7 +35,8 @@ impl FoodBundle {
         grid_x: u32,
         grid_y: u32,
         animation: Handle<AnimationClip>,
-        powerup: Powerup,
+        entry: &PowerupEntry,
+        configuration: &GameConfiguration,
     ) -> Self {
         let cell = Cell {
             x: grid_x,
@@ -38,70 +47,75 @@ impl FoodBundle {
         player.play(animation).repeat();
 
         Self {
-            food: Food(powerup),
-            cell: CellBundle::new(cell, powerup.get_color()),
+            food: Food(entry.id.clone()),
+            cell: CellBundle::new(cell, entry.get_color(), configuration),
             name: Name::new(BREATHE_ANIMATION_NAME),
             animation: player,
         }
     }
 }
 
-pub fn random_pos_food_bundle(
+// Food for random free cells, everything in `taken` is skipped. Fewer foods come back
+// when the board is full.
+pub fn random_pos_food_bundle<'a>(
     animation: Handle<AnimationClip>,
-    query: Query<&Cell, Without<Food>>,
-    game_configuration: Res<GameConfiguration>,
+    taken: impl Iterator<Item = &'a Cell>,
+    game_configuration: &GameConfiguration,
+    rng: &mut GameRng,
+    registry: &PowerupRegistry,
     powerup: Option<Powerup>,
     amount: Option<u32>,
 ) -> Vec<FoodBundle> {
-    // My crude logic, this can be improved in many ways.
-    // 1. Get a Vec of game field size filled with all possible positions.
-    // 2. Remove taken positions.
-    // 3. Pick a random position from the remaining ones.
-
-    let mut taken_pos: Vec<Cell> = game_configuration.field.clone();
-
-    for cell in query.iter() {
-        let id = taken_pos.iter().position(|&c| c == *cell);
-        if let Some(id) = id {
-            taken_pos.remove(id);
-        };
-    }
-
-    let amount: u32 = amount.unwrap_or(1);
-
-    let mut food_bundles: Vec<FoodBundle> = Vec::new();
-
-    for _ in 0..amount {
-        let random_pos_id = rand::thread_rng().gen_range(0..taken_pos.len());
-        let random_pos = taken_pos[random_pos_id];
-        taken_pos.remove(random_pos_id);
-
-        let powerup = powerup.unwrap_or(Powerup::get_random_powerup());
-
-        let food_bundle = FoodBundle::new(random_pos.x, random_pos.y, animation.clone(), powerup);
-        food_bundles.push(food_bundle);
-    }
-
-    food_bundles
+    rules::place_foods(
+        &game_configuration.field,
+        taken,
+        game_configuration.food_spawns(),
+        registry,
+        powerup,
+        amount.unwrap_or(1),
+        rng,
+    )
+    .into_iter()
+    .map(|(cell, entry)| {
+        FoodBundle::new(
+            cell.x,
+            cell.y,
+            animation.clone(),
+            &entry,
+            game_configuration,
+        )
+    })
+    .collect()
 }
 
 fn spawn_first_food(
     mut commands: Commands,
     animation_handles: Res<AnimationHandles>,
-    query: Query<&Cell, Without<Food>>,
     game_configuration: Res<GameConfiguration>,
+    mut rng: ResMut<GameRng>,
+    registry: Res<PowerupRegistry>,
 ) {
-    let food_bundle = random_pos_food_bundle(
+    // The snakes are spawned on the same frame, their start cells are taken already.
+    let starts: Vec<Cell> = game_configuration
+        .start_positions()
+        .into_iter()
+        .map(|(cell, _)| cell)
+        .collect();
+
+    let food_bundles = random_pos_food_bundle(
         animation_handles.breathe.clone(),
-        query,
-        game_configuration,
+        starts.iter(),
+        &game_configuration,
+        &mut rng,
+        &registry,
         Some(Powerup::Normal),
         None,
-    )
-    .pop()
-    .unwrap();
+    );
 
-    commands.spawn(food_bundle);
+    // A board taken up by snakes gets no food, like in `SnakeSim`.
+    for food_bundle in food_bundles {
+        commands.spawn(food_bundle);
+    }
 }
 
 fn despawn_food_on_eat(mut ev_eat: EventReader<EatEvent>, mut commands: Commands) {
@@ -110,37 +124,70 @@ fn despawn_food_on_eat(mut ev_eat: EventReader<EatEvent>, mut commands: Commands
     }
 }
 
+// Spawns the foods of every feast and replaces the last foods, handling the eats in the
+// order `SnakeSim::eat` does. With several snakes more than one food can be eaten on a
+// tick, cells picked for one eat are taken for the next.
+#[allow(clippy::too_many_arguments)]
 fn spawn_food_on_eat(
     mut ev_eat: EventReader<EatEvent>,
     mut commands: Commands,
     animation_handles: Res<AnimationHandles>,
-    query: Query<&Cell, Without<Food>>,
-    food_on_field: Query<&Food>,
+    // Snakes, walls and food, none of them can get new food on top.
+    query: Query<&Cell>,
+    food_on_field: Query<Entity, With<Food>>,
     game_configuration: Res<GameConfiguration>,
+    mut rng: ResMut<GameRng>,
+    registry: Res<PowerupRegistry>,
 ) {
-    let mut iter = ev_eat.read();
-
-    // As I expect to have only one event at a time, I don't need to iterate over it.
-    // So I just consume the iterator if it's not empty.
-    if iter.len() > 0 {
-        let event = iter.next();
-
-        for _ in iter {}
-
-        if let Some(ev) = event {
-            if ev.food.0 != Powerup::Feast && food_on_field.iter().len() == 0 {
-                let food_bundle = random_pos_food_bundle(
-                    animation_handles.breathe.clone(),
-                    query,
-                    game_configuration,
-                    None,
-                    None,
-                )
-                .pop()
-                .unwrap();
-
-                commands.spawn(food_bundle);
-            }
+    let eaten: Vec<&EatEvent> = ev_eat.read().collect();
+
+    // Eaten food is only gone at the end of the frame.
+    let mut foods_left = food_on_field
+        .iter()
+        .filter(|food| !eaten.iter().any(|ev| ev.id == *food))
+        .count();
+    let mut taken: Vec<Cell> = query.iter().copied().collect();
+
+    for (i, ev) in eaten.iter().enumerate() {
+        // Two heads meeting on a food only eat it once.
+        if eaten[..i].iter().any(|other| other.id == ev.id) {
+            continue;
+        }
+
+        let entry = registry.entry(&ev.food.0);
+
+        // Nothing is spawned once the board is full.
+        let mut food_bundles = random_pos_food_bundle(
+            animation_handles.breathe.clone(),
+            taken.iter(),
+            &game_configuration,
+            &mut rng,
+            &registry,
+            None,
+            Some(rules::feast_foods(&entry)),
+        );
+        taken.extend(food_bundles.iter().map(|bundle| bundle.cell.cell));
+
+        // Feast food counts as left. A replacement for another snake doesn't,
+        // every snake eating one of the last foods gets a new one.
+        foods_left += food_bundles.len();
+
+        if rules::replaces_food(&entry, foods_left) {
+            let replacement = random_pos_food_bundle(
+                animation_handles.breathe.clone(),
+                taken.iter(),
+                &game_configuration,
+                &mut rng,
+                &registry,
+                None,
+                None,
+            );
+            taken.extend(replacement.iter().map(|bundle| bundle.cell.cell));
+            food_bundles.extend(replacement);
+        }
+
+        for food_bundle in food_bundles {
+            commands.spawn(food_bundle);
         }
     }
 }
@@ -155,15 +202,21 @@ pub struct FoodPlugin;
 
 impl Plugin for FoodPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::InGame), spawn_first_food)
-            .add_systems(Update, spawn_food_on_eat.in_set(InGameSet::SpawnEntities))
-            .add_systems(
-                Update,
-                despawn_food_on_eat.in_set(InGameSet::DespawnEntities),
-            )
-            .add_systems(
-                OnExit(GameState::InGame),
-                destroy_food.in_set(InGameSet::DespawnEntities),
-            );
+        app.add_systems(
+            OnEnter(GameState::InGame),
+            spawn_first_food.after(reseed_game_rng),
+        )
+        // Food is replaced on the frame it is eaten, so it is on the board before the next tick
+        // however soon that comes.
+        .add_systems(
+            Update,
+            (despawn_food_on_eat, spawn_food_on_eat)
+                .after(send_eat_event)
+                .in_set(InGameSet::CollisionDetection),
+        )
+        .add_systems(
+            OnExit(GameState::InGame),
+            destroy_food.in_set(InGameSet::DespawnEntities),
+        );
     }
 }
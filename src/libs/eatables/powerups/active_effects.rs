@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::libs::{
+    eatables::eat_event::EatEvent, game_configuration::GameConfiguration, game_states::GameState,
+    player::PlayerId, schedule::InGameSet, sim::rules,
+};
+
+use super::{
+    powerup::Powerup,
+    registry::{PowerupEntry, PowerupRegistry},
+};
+
+// What happens when a powerup is eaten while its effect is still running.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub enum StackingPolicy {
+    // Start the duration over.
+    #[default]
+    Refresh,
+    // Add the duration to what is left.
+    Extend,
+    // Run another instance next to the current one, each applies its strength.
+    Stack,
+    // Keep the running effect as it is.
+    Ignore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActiveEffect {
+    // Registry entry the effect was started from.
+    pub id: String,
+    pub powerup: Powerup,
+    pub remaining_ticks: u32,
+    pub duration: u32,
+}
+
+// Every timed powerup that is running. Counts ticks, not time,
+// so an effect lasts the same however fast the game runs.
+// The resource holds the effects shared by the whole board (speed changes),
+// the component on each snake head the ones of that snake only (Ghost, score multipliers).
+#[derive(Resource, Component, Default, Clone, Debug, PartialEq)]
+pub struct ActiveEffects {
+    effects: Vec<ActiveEffect>,
+}
+
+impl ActiveEffects {
+    pub fn start(&mut self, entry: &PowerupEntry) {
+        let duration = entry.duration;
+
+        if duration == 0 {
+            return;
+        }
+
+        let running = self
+            .effects
+            .iter_mut()
+            .filter(|effect| effect.id == entry.id)
+            .max_by_key(|effect| effect.remaining_ticks);
+
+        match (running, entry.stacking) {
+            (Some(effect), StackingPolicy::Refresh) => {
+                effect.remaining_ticks = duration;
+                effect.duration = duration;
+            }
+            (Some(effect), StackingPolicy::Extend) => {
+                effect.remaining_ticks += duration;
+                effect.duration = effect.remaining_ticks;
+            }
+            (Some(_), StackingPolicy::Ignore) => {}
+            (None, _) | (Some(_), StackingPolicy::Stack) => self.effects.push(ActiveEffect {
+                id: entry.id.clone(),
+                powerup: entry.effect,
+                remaining_ticks: duration,
+                duration,
+            }),
+        }
+    }
+
+    // Counts one tick down and returns the powerups that are no longer active.
+    pub fn tick(&mut self) -> Vec<Powerup> {
+        for effect in self.effects.iter_mut() {
+            effect.remaining_ticks -= 1;
+        }
+
+        let mut ended: Vec<Powerup> = Vec::new();
+
+        for effect in self.effects.iter() {
+            if effect.remaining_ticks == 0 && !ended.contains(&effect.powerup) {
+                ended.push(effect.powerup);
+            }
+        }
+
+        self.effects.retain(|effect| effect.remaining_ticks > 0);
+        ended.retain(|powerup| !self.is_active(*powerup));
+
+        ended
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveEffect> {
+        self.effects.iter()
+    }
+
+    pub fn is_active(&self, powerup: Powerup) -> bool {
+        self.effects.iter().any(|effect| effect.powerup == powerup)
+    }
+
+    pub fn remaining_ticks(&self, powerup: Powerup) -> u32 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.powerup == powerup)
+            .map(|effect| effect.remaining_ticks)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Seconds per tick, every running speed effect scales the `base` rate.
+    pub fn tick_rate(&self, base: f32, registry: &PowerupRegistry) -> f32 {
+        let factors = self
+            .effects
+            .iter()
+            .filter(|effect| effect.powerup.changes_speed())
+            .map(|effect| registry.entry(&effect.id).magnitude);
+
+        rules::tick_rate(base, factors)
+    }
+
+    // Points for one eaten food.
+    pub fn score_multiplier(&self, registry: &PowerupRegistry) -> u32 {
+        let multipliers = self
+            .effects
+            .iter()
+            .map(|effect| registry.entry(&effect.id).score_multiplier);
+
+        rules::score_multiplier(multipliers)
+    }
+}
+
+fn start_effects_on_eat(
+    mut ev_eat: EventReader<EatEvent>,
+    mut effects: ResMut<ActiveEffects>,
+    mut snake_effects: Query<(&mut ActiveEffects, &PlayerId)>,
+    registry: Res<PowerupRegistry>,
+) {
+    for ev in ev_eat.read() {
+        let entry = registry.entry(&ev.food.0);
+
+        if entry.is_shared() {
+            effects.start(&entry);
+        }
+
+        if entry.is_personal() {
+            for (mut snake_effects, player) in snake_effects.iter_mut() {
+                if *player == ev.player {
+                    snake_effects.start(&entry);
+                }
+            }
+        }
+    }
+}
+
+fn advance_effects(
+    configuration: Res<GameConfiguration>,
+    mut effects: ResMut<ActiveEffects>,
+    mut snake_effects: Query<&mut ActiveEffects>,
+) {
+    if configuration.tick_timer.just_finished() {
+        effects.tick();
+
+        for mut snake_effects in snake_effects.iter_mut() {
+            snake_effects.tick();
+        }
+    }
+}
+
+fn update_game_speed(
+    mut configuration: ResMut<GameConfiguration>,
+    effects: Res<ActiveEffects>,
+    registry: Res<PowerupRegistry>,
+) {
+    let game_speed = effects.tick_rate(configuration.current_difficulty.get_tick_rate(), &registry);
+
+    if configuration.tick_timer.duration() != Duration::from_secs_f32(game_speed) {
+        configuration.set_game_speed(game_speed);
+    }
+}
+
+fn reset_effects(mut effects: ResMut<ActiveEffects>, mut configuration: ResMut<GameConfiguration>) {
+    *effects = ActiveEffects::default();
+
+    let difficulty = configuration.current_difficulty;
+    configuration.set_difficulty_and_reset_timer(difficulty);
+}
+
+pub struct ActiveEffectsPlugin;
+
+impl Plugin for ActiveEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ActiveEffects::default())
+            .add_systems(
+                Update,
+                (start_effects_on_eat, advance_effects)
+                    .chain()
+                    .in_set(InGameSet::EntityUpdates),
+            )
+            // Changing the speed resets the tick timer, so it's done after everything
+            // that looks at `tick_timer.just_finished()` this frame.
+            .add_systems(
+                Update,
+                update_game_speed.in_set(InGameSet::CollisionDetection),
+            )
+            .add_systems(OnExit(GameState::InGame), reset_effects);
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::libs::{schedule::InGameSet, snake::Snake};
+
+use super::{active_effects::ActiveEffects, powerup::Powerup};
+
+const GHOST_ALPHA: f32 = 0.35;
+// The snake blinks for this many ticks before the ghost wears off.
+const GHOST_WARNING_TICKS: u32 = 5;
+
+// Fades only the snakes that ate a ghost.
+fn render_ghost(snakes: Query<(&Snake, &ActiveEffects)>, mut sprites: Query<&mut Sprite>) {
+    for (snake, effects) in snakes.iter() {
+        let alpha = if effects.is_active(Powerup::Ghost) {
+            let remaining_ticks = effects.remaining_ticks(Powerup::Ghost);
+
+            // Blink every tick when the effect is about to end.
+            if remaining_ticks <= GHOST_WARNING_TICKS && remaining_ticks.is_multiple_of(2) {
+                1.0
+            } else {
+                GHOST_ALPHA
+            }
+        } else {
+            1.0
+        };
+
+        for part in snake.parts.iter() {
+            if let Ok(mut sprite) = sprites.get_mut(*part)
+                && sprite.color.a() != alpha
+            {
+                sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
+
+pub struct GhostPowerupPlugin;
+
+impl Plugin for GhostPowerupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_ghost.in_set(InGameSet::GlobalPostionUpdates));
+    }
+}
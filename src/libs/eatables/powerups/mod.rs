@@ -1,4 +1,5 @@
-pub mod feast;
+pub mod active_effects;
+pub mod ghost;
 pub mod powerup;
+pub mod registry;
 pub mod shorten;
-pub mod slowdown;
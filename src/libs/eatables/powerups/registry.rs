@@ -0,0 +1,260 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{active_effects::StackingPolicy, powerup::Powerup};
+
+pub const POWERUPS_FILE: &str = "powerups.ron";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PowerupEntry {
+    pub id: String,
+    pub effect: Powerup,
+    pub color: (f32, f32, f32),
+    // Relative to the other entries, they don't have to sum up to 1.
+    pub weight: f32,
+    // Ticks the effect lasts, 0 for instant effects.
+    pub duration: u32,
+    // Tick rate factor for Slowdown and Frenzy, parts removed by Shorten, food spawned by Feast.
+    pub magnitude: f32,
+    // Points for every food eaten while the effect is active.
+    #[serde(default = "default_score_multiplier")]
+    pub score_multiplier: u32,
+    // What eating it does to an effect of the same kind that is still running.
+    #[serde(default)]
+    pub stacking: StackingPolicy,
+}
+
+fn default_score_multiplier() -> u32 {
+    1
+}
+
+impl PowerupEntry {
+    pub fn get_color(&self) -> Color {
+        Color::rgb(self.color.0, self.color.1, self.color.2)
+    }
+
+    // Speed changes run for every snake on the board.
+    pub fn is_shared(&self) -> bool {
+        self.effect.changes_speed()
+    }
+
+    // Everything else, like Ghost or a score multiplier, only for the snake that ate it.
+    pub fn is_personal(&self) -> bool {
+        !self.effect.changes_speed() || self.score_multiplier > 1
+    }
+}
+
+// Built-in values, used until `powerups.ron` is loaded or when it lacks an effect.
+fn default_entry(powerup: Powerup) -> PowerupEntry {
+    let (id, color, weight, duration, magnitude, score_multiplier) = match powerup {
+        Powerup::Normal => ("normal", (0.9, 0.1, 0.1), 70.0, 0, 0.0, 1),
+        Powerup::Slowdown => ("slowdown", (0.0, 0.0, 0.9), 5.0, 20, 2.0, 1),
+        Powerup::Shorten => ("shorten", (0.9, 0.9, 0.0), 10.0, 0, 3.0, 1),
+        Powerup::Feast => ("feast", (0.0, 0.9, 0.0), 5.0, 0, 4.0, 1),
+        Powerup::Ghost => ("ghost", (0.7, 0.5, 0.9), 5.0, 25, 1.0, 1),
+        Powerup::Frenzy => ("frenzy", (1.0, 0.5, 0.0), 5.0, 30, 0.5, 3),
+    };
+
+    PowerupEntry {
+        id: id.to_string(),
+        effect: powerup,
+        color,
+        weight,
+        duration,
+        magnitude,
+        score_multiplier,
+        stacking: StackingPolicy::default(),
+    }
+}
+
+#[derive(Resource, Asset, TypePath, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PowerupRegistry {
+    pub powerups: Vec<PowerupEntry>,
+}
+
+impl Default for PowerupRegistry {
+    fn default() -> Self {
+        Self {
+            powerups: Powerup::ALL.into_iter().map(default_entry).collect(),
+        }
+    }
+}
+
+impl PowerupRegistry {
+    // First entry with the effect, for food that has to be of a certain kind.
+    pub fn get(&self, powerup: Powerup) -> PowerupEntry {
+        self.powerups
+            .iter()
+            .find(|entry| entry.effect == powerup)
+            .cloned()
+            .unwrap_or_else(|| default_entry(powerup))
+    }
+
+    // Entry a food was spawned from. Built-in ids still resolve when the file lacks them,
+    // anything else falls back to normal food.
+    pub fn entry(&self, id: &str) -> PowerupEntry {
+        self.powerups
+            .iter()
+            .find(|entry| entry.id == id)
+            .cloned()
+            .or_else(|| {
+                Powerup::ALL
+                    .into_iter()
+                    .map(default_entry)
+                    .find(|entry| entry.id == id)
+            })
+            .unwrap_or_else(|| default_entry(Powerup::Normal))
+    }
+
+    pub fn get_color(&self, powerup: Powerup) -> Color {
+        self.get(powerup).get_color()
+    }
+
+    // Picks an entry with a chance proportional to its weight.
+    pub fn get_random_entry(&self, rng: &mut impl Rng) -> PowerupEntry {
+        let total: f32 = self.powerups.iter().map(|entry| entry.weight).sum();
+
+        if total <= 0.0 {
+            return self.get(Powerup::Normal);
+        }
+
+        let mut random_number = rng.gen_range(0.0..total);
+
+        for entry in self.powerups.iter().filter(|entry| entry.weight > 0.0) {
+            if random_number < entry.weight {
+                return entry.clone();
+            }
+
+            random_number -= entry.weight;
+        }
+
+        // Float rounding can leave a tiny remainder past the last entry.
+        self.powerups
+            .iter()
+            .rev()
+            .find(|entry| entry.weight > 0.0)
+            .cloned()
+            .unwrap_or_else(|| self.get(Powerup::Normal))
+    }
+}
+
+#[derive(Debug)]
+pub enum PowerupRegistryError {
+    Io(std::io::Error),
+    Format(String),
+    NegativeWeight(String),
+    DuplicateId(String),
+}
+
+impl fmt::Display for PowerupRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PowerupRegistryError::Io(err) => write!(f, "can't read powerups file: {err}"),
+            PowerupRegistryError::Format(err) => write!(f, "bad powerups file: {err}"),
+            PowerupRegistryError::NegativeWeight(id) => {
+                write!(f, "powerup '{id}' has a negative weight")
+            }
+            PowerupRegistryError::DuplicateId(id) => {
+                write!(f, "more than one powerup with id '{id}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PowerupRegistryError {}
+
+impl PowerupRegistry {
+    pub fn from_ron(text: &str) -> Result<Self, PowerupRegistryError> {
+        let registry: Self =
+            ron::from_str(text).map_err(|err| PowerupRegistryError::Format(err.to_string()))?;
+
+        for (id, entry) in registry.powerups.iter().enumerate() {
+            if entry.weight < 0.0 {
+                return Err(PowerupRegistryError::NegativeWeight(entry.id.clone()));
+            }
+
+            // Foods refer to their entry by id, several entries may share an effect.
+            if registry.powerups[..id]
+                .iter()
+                .any(|other| other.id == entry.id)
+            {
+                return Err(PowerupRegistryError::DuplicateId(entry.id.clone()));
+            }
+        }
+
+        Ok(registry)
+    }
+}
+
+#[derive(Default)]
+pub struct PowerupRegistryLoader;
+
+impl AssetLoader for PowerupRegistryLoader {
+    type Asset = PowerupRegistry;
+    type Settings = ();
+    type Error = PowerupRegistryError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<PowerupRegistry, PowerupRegistryError>> {
+        Box::pin(async move {
+            let mut text = String::new();
+            reader
+                .read_to_string(&mut text)
+                .await
+                .map_err(PowerupRegistryError::Io)?;
+
+            PowerupRegistry::from_ron(&text)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["powerups.ron"]
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PowerupRegistryHandle(Handle<PowerupRegistry>);
+
+fn load_powerups(mut handle: ResMut<PowerupRegistryHandle>, asset_server: Res<AssetServer>) {
+    handle.0 = asset_server.load(POWERUPS_FILE);
+}
+
+// Copies the loaded asset into the resource, also picks up edits when asset hot reloading is on.
+pub fn update_powerup_registry(
+    mut ev_asset: EventReader<AssetEvent<PowerupRegistry>>,
+    handle: Res<PowerupRegistryHandle>,
+    assets: Res<Assets<PowerupRegistry>>,
+    mut registry: ResMut<PowerupRegistry>,
+) {
+    for ev in ev_asset.read() {
+        if (ev.is_loaded_with_dependencies(&handle.0) || ev.is_modified(&handle.0))
+            && let Some(loaded) = assets.get(&handle.0)
+        {
+            *registry = loaded.clone();
+        }
+    }
+}
+
+pub struct PowerupRegistryPlugin;
+
+impl Plugin for PowerupRegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PowerupRegistry>()
+            .init_asset_loader::<PowerupRegistryLoader>()
+            .insert_resource(PowerupRegistry::default())
+            .insert_resource(PowerupRegistryHandle::default())
+            .add_systems(Startup, load_powerups)
+            .add_systems(Update, update_powerup_registry);
+    }
+}
@@ -2,21 +2,29 @@ use bevy::prelude::*;
 
 use crate::libs::{
     eatables::eat_event::EatEvent,
+    player::PlayerId,
     schedule::InGameSet,
+    sim::rules,
     snake::{Snake, Tail},
 };
 
-use super::powerup::Powerup;
+use super::registry::PowerupRegistry;
 
 fn shorten_snake_on_powerup(
     mut ev_eat: EventReader<EatEvent>,
     mut commands: Commands,
     query: Query<Entity, With<Tail>>,
-    mut snake: ResMut<Snake>,
+    mut snakes: Query<(&mut Snake, &PlayerId)>,
+    registry: Res<PowerupRegistry>,
 ) {
     for ev in ev_eat.read() {
-        if ev.food.0 == Powerup::Shorten {
-            Snake::remove_tails(&mut commands, &query, &mut snake, Powerup::Shorten.power());
+        let entry = registry.entry(&ev.food.0);
+
+        for (mut snake, player) in snakes.iter_mut() {
+            if *player == ev.player {
+                let amount = rules::tails_removed(&entry, snake.parts.len());
+                snake.remove_tails(&mut commands, &query, amount);
+            }
         }
     }
 }
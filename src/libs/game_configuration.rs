@@ -1,14 +1,22 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
 
 use super::{
     cell::Cell,
-    globals::{BASE_GAME_SPEED, GRID_SIZE},
+    game_states::GameState,
+    globals::{BASE_GAME_SPEED, DEFAULT_GRID_CELL, DEFAULT_GRID_HEIGHT, DEFAULT_GRID_WIDTH},
+    input::direction::Direction,
+    level::Level,
+    player::{PlayerId, MAX_BOTS},
+    replay::playback::ReplayPlayback,
     schedule::InGameSet,
+    sim::bot::BotLevel,
+    utils::cli_arg,
 };
 
-#[derive(Default, Reflect, Clone, Copy)]
+#[derive(Default, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GameDifficulty {
     Easy,
     #[default]
@@ -28,27 +36,246 @@ impl GameDifficulty {
     }
 }
 
-#[derive(Resource, Reflect)]
+#[derive(Default, Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GridPreset {
+    Small,
+    #[default]
+    Classic,
+    Wide,
+}
+
+impl GridPreset {
+    // Width, height and cell size in pixels.
+    pub fn get_dimensions(&self) -> (u32, u32, f32) {
+        match self {
+            GridPreset::Small => (9, 9, 80.0),
+            GridPreset::Classic => (DEFAULT_GRID_WIDTH, DEFAULT_GRID_HEIGHT, DEFAULT_GRID_CELL),
+            GridPreset::Wide => (21, 13, 45.0),
+        }
+    }
+}
+
+// What happens when the head leaves the board.
+#[derive(Default, Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BoundaryMode {
+    // Head comes out on the opposite edge.
+    #[default]
+    Wrap,
+    // Head hits a wall and the game ends.
+    Solid,
+}
+
+// How many snakes share the board.
+#[derive(Default, Reflect, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Single,
+    // Two snakes, the last one alive wins the round.
+    Versus,
+}
+
+impl GameMode {
+    pub fn player_count(&self) -> usize {
+        match self {
+            GameMode::Single => 1,
+            GameMode::Versus => 2,
+        }
+    }
+}
+
+#[derive(Resource, Reflect, Clone)]
 pub struct GameConfiguration {
     pub current_difficulty: GameDifficulty,
     pub tick_timer: Timer,
+    pub grid_width: u32,
+    pub grid_height: u32,
+    pub cell_size: f32,
+    pub boundary_mode: BoundaryMode,
+    pub mode: GameMode,
+    // CPU snakes next to the players.
+    pub bots: usize,
+    pub bot_level: BotLevel,
+    pub level: Option<Level>,
+    // Ticks since the game started.
+    pub tick: u64,
     pub field: Vec<Cell>,
+    // Fixed seed for `GameRng`, a fresh one is rolled every game if not set.
+    pub seed: Option<u64>,
+    // Slide sprites between cells during a tick instead of jumping on it. Only changes rendering.
+    pub smooth_movement: bool,
+    // Draw snakes with the textured pieces of `SKIN_FILE` instead of plain shapes.
+    pub snake_skin: bool,
 }
 
 impl GameConfiguration {
     pub fn new(difficulty: GameDifficulty) -> Self {
-        let mut field: Vec<Cell> = Vec::with_capacity((GRID_SIZE * GRID_SIZE) as usize);
+        let mut configuration = Self {
+            current_difficulty: GameDifficulty::default(),
+            tick_timer: Timer::from_seconds(difficulty.get_tick_rate(), TimerMode::Repeating),
+            grid_width: 0,
+            grid_height: 0,
+            cell_size: 0.,
+            boundary_mode: BoundaryMode::default(),
+            mode: GameMode::default(),
+            bots: 0,
+            bot_level: BotLevel::default(),
+            level: None,
+            tick: 0,
+            field: Vec::new(),
+            seed: seed_from_env(),
+            smooth_movement: false,
+            snake_skin: false,
+        };
 
-        for y in 0..GRID_SIZE {
-            for x in 0..GRID_SIZE {
-                field.push(Cell { x, y });
+        configuration.set_grid(DEFAULT_GRID_WIDTH, DEFAULT_GRID_HEIGHT, DEFAULT_GRID_CELL);
+        configuration
+    }
+
+    // Sets an empty board, removing the level if there was one.
+    pub fn set_grid(&mut self, width: u32, height: u32, cell_size: f32) {
+        self.grid_width = width;
+        self.grid_height = height;
+        self.cell_size = cell_size;
+        self.level = None;
+
+        self.field = Vec::with_capacity((width * height) as usize);
+
+        for y in 0..height {
+            for x in 0..width {
+                self.field.push(Cell { x, y });
             }
         }
+    }
 
-        Self {
-            current_difficulty: GameDifficulty::default(),
-            tick_timer: Timer::from_seconds(difficulty.get_tick_rate(), TimerMode::Repeating),
-            field,
+    pub fn set_grid_preset(&mut self, preset: GridPreset) {
+        let (width, height, cell_size) = preset.get_dimensions();
+        self.set_grid(width, height, cell_size);
+    }
+
+    pub fn set_level(&mut self, level: Level) {
+        // Keep the window about the size of the classic board.
+        let longest_side = level.width.max(level.height) as f32;
+        let cell_size = (DEFAULT_GRID_CELL * DEFAULT_GRID_WIDTH as f32 / longest_side)
+            .min(DEFAULT_GRID_CELL)
+            .floor();
+
+        self.set_grid(level.width, level.height, cell_size);
+        // Walls are not part of the field, so nothing is ever placed on them.
+        self.field.retain(|cell| !level.walls.contains(cell));
+        self.level = Some(level);
+    }
+
+    pub fn grid_center(&self) -> Cell {
+        Cell {
+            x: self.grid_width / 2,
+            y: self.grid_height / 2,
+        }
+    }
+
+    pub fn start_position(&self) -> (Cell, Direction) {
+        let (cell, direction) = match &self.level {
+            Some(level) => (level.start, level.start_direction),
+            None => (self.grid_center(), Direction::Up),
+        };
+
+        (self.closest_free_cell(cell, &[]), direction)
+    }
+
+    // `cell` itself when a snake can start there, otherwise the closest field cell not in `taken`.
+    fn closest_free_cell(&self, cell: Cell, taken: &[Cell]) -> Cell {
+        let distance = |other: &Cell| other.x.abs_diff(cell.x) + other.y.abs_diff(cell.y);
+
+        self.field
+            .iter()
+            .filter(|other| !taken.contains(other))
+            .min_by_key(|other| distance(other))
+            .copied()
+            .unwrap_or(cell)
+    }
+
+    // Players and bots on the board.
+    pub fn snake_count(&self) -> usize {
+        self.mode.player_count() + self.bots.min(MAX_BOTS)
+    }
+
+    pub fn is_bot(&self, player: PlayerId) -> bool {
+        player.0 >= self.mode.player_count()
+    }
+
+    // Start of every snake. In versus the second snake starts mirrored through the
+    // board center, bots start as far as possible from everyone else.
+    pub fn start_positions(&self) -> Vec<(Cell, Direction)> {
+        let first = match (&self.level, self.mode) {
+            (None, GameMode::Versus) => {
+                let cell = Cell {
+                    x: self.grid_width / 3,
+                    y: self.grid_height / 2,
+                };
+
+                (self.closest_free_cell(cell, &[]), Direction::Up)
+            }
+            _ => self.start_position(),
+        };
+
+        let mut starts = vec![first];
+
+        if self.mode == GameMode::Versus {
+            let (cell, direction) = first;
+            let mirrored = Cell {
+                x: self.grid_width - 1 - cell.x,
+                y: self.grid_height - 1 - cell.y,
+            };
+
+            // Closest free cell, in case the mirrored one is a wall or the first snake's start.
+            let second = self.closest_free_cell(mirrored, &[cell]);
+
+            starts.push((second, direction.opposite()));
+        }
+
+        for _ in 0..self.bots.min(MAX_BOTS) {
+            let distance = |other: &Cell| {
+                starts
+                    .iter()
+                    .map(|(start, _)| start.x.abs_diff(other.x) + start.y.abs_diff(other.y))
+                    .min()
+                    .unwrap_or(0)
+            };
+
+            let Some(cell) = self.field.iter().copied().max_by_key(|cell| distance(cell)) else {
+                break;
+            };
+
+            // Head towards the middle of the board.
+            let direction = if cell.y < self.grid_height / 2 {
+                Direction::Down
+            } else {
+                Direction::Up
+            };
+
+            starts.push((cell, direction));
+        }
+
+        starts
+    }
+
+    pub fn walls(&self) -> &[Cell] {
+        match &self.level {
+            Some(level) => &level.walls,
+            None => &[],
+        }
+    }
+
+    pub fn food_spawns(&self) -> &[Cell] {
+        match &self.level {
+            Some(level) => &level.food_spawns,
+            None => &[],
+        }
+    }
+
+    pub fn window_size(&self) -> Vec2 {
+        Vec2 {
+            x: self.cell_size * self.grid_width as f32,
+            y: self.cell_size * self.grid_height as f32,
         }
     }
 
@@ -64,6 +291,22 @@ impl GameConfiguration {
             .set_duration(Duration::from_secs_f32(game_speed));
         self.tick_timer.unpause();
     }
+
+    pub fn advance(&mut self, delta: Duration) {
+        if self.tick_timer.tick(delta).just_finished() {
+            self.tick += 1;
+        }
+    }
+
+    // Index of the tick the next snake move happens on.
+    // Equals `tick` on the frame the move is made.
+    pub fn upcoming_tick(&self) -> u64 {
+        if self.tick_timer.just_finished() {
+            self.tick
+        } else {
+            self.tick + 1
+        }
+    }
 }
 
 impl Default for GameConfiguration {
@@ -72,8 +315,32 @@ impl Default for GameConfiguration {
     }
 }
 
+// A seed can be passed as `--seed <number>` or through the `SNAKE_SEED` variable.
+fn seed_from_env() -> Option<u64> {
+    cli_arg("--seed")
+        .or_else(|| std::env::var("SNAKE_SEED").ok())
+        .and_then(|seed| seed.parse().ok())
+}
+
 fn advance_timer(mut configuration: ResMut<GameConfiguration>, time: Res<Time>) {
-    configuration.tick_timer.tick(time.delta());
+    configuration.advance(time.delta());
+}
+
+fn fit_window_to_grid(
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    configuration: Res<GameConfiguration>,
+) {
+    let size = configuration.window_size();
+
+    for mut window in windows.iter_mut() {
+        if window.resolution.width() != size.x || window.resolution.height() != size.y {
+            window.resolution.set(size.x, size.y);
+        }
+    }
+}
+
+fn reset_tick(mut configuration: ResMut<GameConfiguration>) {
+    configuration.tick = 0;
 }
 
 pub struct GameConfigurationPlugin;
@@ -82,6 +349,13 @@ impl Plugin for GameConfigurationPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<GameConfiguration>()
             .insert_resource(GameConfiguration::default())
-            .add_systems(Update, advance_timer.in_set(InGameSet::DespawnEntities));
+            .add_systems(OnEnter(GameState::InGame), reset_tick)
+            .add_systems(Update, fit_window_to_grid)
+            .add_systems(
+                Update,
+                advance_timer
+                    .in_set(InGameSet::DespawnEntities)
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            );
     }
 }
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::{game_configuration::GameConfiguration, game_states::GameState};
+
+// Every random decision of a game goes through this, so a seed and the
+// player's inputs are enough to reproduce the whole game.
+// ChaCha8 gives the same numbers on every platform and `rand` version, unlike `StdRng`,
+// so seeds and replays stay valid.
+#[derive(Resource, Clone, Debug)]
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+pub fn reseed_game_rng(mut rng: ResMut<GameRng>, configuration: Res<GameConfiguration>) {
+    *rng = match configuration.seed {
+        Some(seed) => GameRng::new(seed),
+        None => GameRng::from_entropy(),
+    };
+}
+
+pub struct GameRngPlugin;
+
+impl Plugin for GameRngPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameRng::default())
+            .add_systems(OnEnter(GameState::InGame), reseed_game_rng);
+    }
+}
@@ -6,6 +6,7 @@ pub enum GameState {
     StartMenu,
     InGame,
     FinishMenu,
+    ControlsMenu,
 }
 
 pub struct GameStatatesPlugin;
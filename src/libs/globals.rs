@@ -1,16 +1,22 @@
 use bevy::render::color::Color;
 
-pub const GRID_CELL: f32 = 60.0;
-pub const GRID_SIZE: u32 = 13;
-pub const GRID_CENTER: u32 = GRID_SIZE / 2;
-
-pub const WINDOW_SIZE: f32 = GRID_CELL * GRID_SIZE as f32;
-pub const LEFT_WINDOW_BORDER: f32 = -WINDOW_SIZE / 2.;
-pub const TOP_WINDOW_BORDER: f32 = WINDOW_SIZE / 2.;
+pub const DEFAULT_GRID_CELL: f32 = 60.0;
+pub const DEFAULT_GRID_WIDTH: u32 = 13;
+pub const DEFAULT_GRID_HEIGHT: u32 = 13;
 
 pub const BACKGROUND_COLOR: Color = Color::rgb(0.24, 0.25, 0.24);
+pub const BORDER_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+pub const OBSTACLE_COLOR: Color = Color::rgb(0.45, 0.42, 0.38);
 
 pub const HEAD_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
 pub const TAIL_COLOR: Color = Color::rgb(0.15, 0.79, 0.58);
+pub const PLAYER_TWO_HEAD_COLOR: Color = Color::rgb(0.98, 0.85, 0.55);
+pub const PLAYER_TWO_TAIL_COLOR: Color = Color::rgb(0.85, 0.35, 0.75);
+pub const BOT_HEAD_COLOR: Color = Color::rgb(0.6, 0.6, 0.6);
+pub const BOT_TAIL_COLORS: [Color; 3] = [
+    Color::rgb(0.35, 0.5, 0.85),
+    Color::rgb(0.85, 0.55, 0.2),
+    Color::rgb(0.55, 0.55, 0.3),
+];
 
 pub const BASE_GAME_SPEED: f32 = 0.25;
@@ -0,0 +1,299 @@
+use std::{collections::HashMap, fmt, fs, io, path::PathBuf, sync::Mutex, time::Duration};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    death::DeathEvent,
+    game_configuration::{GameConfiguration, GameDifficulty, GameMode},
+    game_states::GameState,
+    player::PlayerId,
+    replay::playback::ReplayPlayback,
+    schedule::InGameSet,
+    score::Score,
+    snake::Snake,
+    utils::data_dir,
+};
+
+pub const HIGH_SCORES_FILE: &str = "high_scores.ron";
+// Entries kept for every difficulty.
+pub const MAX_HIGH_SCORES: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HighScoreEntry {
+    pub score: u32,
+    pub length: u32,
+    pub duration_secs: f32,
+    // Seconds since the Unix epoch.
+    pub date: u64,
+    pub name: Option<String>,
+}
+
+// Best runs for every difficulty, highest score first.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct HighScoreTable {
+    pub entries: HashMap<GameDifficulty, Vec<HighScoreEntry>>,
+}
+
+impl HighScoreTable {
+    pub fn get(&self, difficulty: GameDifficulty) -> &[HighScoreEntry] {
+        self.entries
+            .get(&difficulty)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Place a run with `score` would take, `None` if it doesn't make it into the table.
+    // Ties go below the runs that were there first, runs without points never make it.
+    pub fn rank(&self, difficulty: GameDifficulty, score: u32) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+
+        let rank = self
+            .get(difficulty)
+            .iter()
+            .take_while(|entry| entry.score >= score)
+            .count();
+
+        (rank < MAX_HIGH_SCORES).then_some(rank)
+    }
+
+    pub fn insert(&mut self, difficulty: GameDifficulty, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.rank(difficulty, entry.score)?;
+        let entries = self.entries.entry(difficulty).or_default();
+
+        entries.insert(rank, entry);
+        entries.truncate(MAX_HIGH_SCORES);
+
+        Some(rank)
+    }
+}
+
+#[derive(Debug)]
+pub enum HighScoreError {
+    Io(io::Error),
+    Format(String),
+    NoDataDir,
+}
+
+impl fmt::Display for HighScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HighScoreError::Io(err) => write!(f, "can't access high scores: {err}"),
+            HighScoreError::Format(err) => write!(f, "malformed high scores: {err}"),
+            HighScoreError::NoDataDir => write!(f, "no data directory to keep high scores in"),
+        }
+    }
+}
+
+impl std::error::Error for HighScoreError {}
+
+// Where the table is kept between sessions.
+pub trait HighScoreStorage: Send + Sync {
+    fn load(&self) -> Result<HighScoreTable, HighScoreError>;
+    fn save(&self, table: &HighScoreTable) -> Result<(), HighScoreError>;
+}
+
+// RON file in the platform data directory.
+pub struct FileStorage {
+    pub path: PathBuf,
+}
+
+impl FileStorage {
+    pub fn in_data_dir() -> Result<Self, HighScoreError> {
+        let dir = data_dir().ok_or(HighScoreError::NoDataDir)?;
+
+        Ok(Self {
+            path: dir.join(HIGH_SCORES_FILE),
+        })
+    }
+}
+
+impl HighScoreStorage for FileStorage {
+    fn load(&self) -> Result<HighScoreTable, HighScoreError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            // Nothing is saved before the first game.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(HighScoreTable::default());
+            }
+            Err(err) => return Err(HighScoreError::Io(err)),
+        };
+
+        ron::from_str(&text).map_err(|err| HighScoreError::Format(err.to_string()))
+    }
+
+    fn save(&self, table: &HighScoreTable) -> Result<(), HighScoreError> {
+        let text = ron::ser::to_string_pretty(table, ron::ser::PrettyConfig::default())
+            .map_err(|err| HighScoreError::Format(err.to_string()))?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(HighScoreError::Io)?;
+        }
+
+        fs::write(&self.path, text).map_err(HighScoreError::Io)
+    }
+}
+
+// Keeps scores for the session only, used where there is no file system.
+#[derive(Default)]
+pub struct MemoryStorage {
+    table: Mutex<HighScoreTable>,
+}
+
+impl HighScoreStorage for MemoryStorage {
+    fn load(&self) -> Result<HighScoreTable, HighScoreError> {
+        Ok(self.table.lock().unwrap().clone())
+    }
+
+    fn save(&self, table: &HighScoreTable) -> Result<(), HighScoreError> {
+        *self.table.lock().unwrap() = table.clone();
+        Ok(())
+    }
+}
+
+fn default_storage() -> Box<dyn HighScoreStorage> {
+    if cfg!(target_arch = "wasm32") {
+        return Box::<MemoryStorage>::default();
+    }
+
+    match FileStorage::in_data_dir() {
+        Ok(storage) => Box::new(storage),
+        Err(err) => {
+            warn!("High scores won't be saved: {err}");
+            Box::<MemoryStorage>::default()
+        }
+    }
+}
+
+// Insert this resource after `HighScoresPlugin` to use another storage.
+#[derive(Resource)]
+pub struct HighScores {
+    pub table: HighScoreTable,
+    storage: Box<dyn HighScoreStorage>,
+    // Where the last finished game landed, shown on the finish menu.
+    pub last_rank: Option<usize>,
+}
+
+impl HighScores {
+    pub fn new(storage: Box<dyn HighScoreStorage>) -> Self {
+        let table = storage.load().unwrap_or_else(|err| {
+            error!("Failed to load high scores: {err}");
+            HighScoreTable::default()
+        });
+
+        Self {
+            table,
+            storage,
+            last_rank: None,
+        }
+    }
+
+    pub fn set_name(&mut self, difficulty: GameDifficulty, rank: usize, name: &str) {
+        let name = name.trim();
+
+        if let Some(entry) = self
+            .table
+            .entries
+            .get_mut(&difficulty)
+            .and_then(|entries| entries.get_mut(rank))
+        {
+            entry.name = (!name.is_empty()).then(|| name.to_string());
+            self.save();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = self.storage.save(&self.table) {
+            error!("Failed to save high scores: {err}");
+        }
+    }
+}
+
+// Time spent in game, pauses don't count.
+#[derive(Resource, Default)]
+struct RunTime(Duration);
+
+fn count_run_time(mut run_time: ResMut<RunTime>, time: Res<Time>) {
+    run_time.0 += time.delta();
+}
+
+fn reset_run_time(mut run_time: ResMut<RunTime>, mut high_scores: ResMut<HighScores>) {
+    *run_time = RunTime::default();
+    high_scores.last_rank = None;
+}
+
+// Seconds since the unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+// `SystemTime` is not available in the browser, the JS clock counts milliseconds.
+#[cfg(target_arch = "wasm32")]
+fn now() -> u64 {
+    (js_sys::Date::now() / 1000.) as u64
+}
+
+fn record_high_score(
+    mut ev_death: EventReader<DeathEvent>,
+    mut high_scores: ResMut<HighScores>,
+    score: Res<Score>,
+    snakes: Query<(&Snake, &PlayerId)>,
+    run_time: Res<RunTime>,
+    configuration: Res<GameConfiguration>,
+) {
+    if !ev_death.read().any(|death| death.player == PlayerId::ONE) {
+        return;
+    }
+
+    ev_death.clear();
+
+    // Versus rounds and games against bots are not comparable with solo runs.
+    if configuration.mode != GameMode::Single || configuration.bots > 0 {
+        return;
+    }
+
+    let length = snakes
+        .iter()
+        .find(|(_, player)| **player == PlayerId::ONE)
+        .map_or(0, |(snake, _)| snake.parts.len());
+
+    let entry = HighScoreEntry {
+        score: score.get(PlayerId::ONE),
+        length: length as u32,
+        duration_secs: run_time.0.as_secs_f32(),
+        date: now(),
+        name: None,
+    };
+
+    high_scores.last_rank = high_scores
+        .table
+        .insert(configuration.current_difficulty, entry);
+
+    if high_scores.last_rank.is_some() {
+        high_scores.save();
+    }
+}
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScores::new(default_storage()))
+            .insert_resource(RunTime::default())
+            .add_systems(OnEnter(GameState::InGame), reset_run_time)
+            .add_systems(Update, count_run_time.in_set(InGameSet::EntityUpdates))
+            // Watching a replay doesn't make a new run.
+            .add_systems(
+                Update,
+                record_high_score
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            );
+    }
+}
@@ -1,8 +1,12 @@
 use super::direction::Direction;
+use crate::libs::player::PlayerId;
 use bevy::prelude::*;
 
 #[derive(Event)]
-pub struct ActionMoveEvent(pub Direction);
+pub struct ActionMoveEvent {
+    pub player: PlayerId,
+    pub direction: Direction,
+}
 
 #[derive(Event)]
 pub struct ActionPauseEvent;
@@ -0,0 +1,465 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::libs::{
+    player::{PlayerId, MAX_PLAYERS},
+    utils::data_dir,
+};
+
+pub const BINDINGS_FILE: &str = "bindings.ron";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Pause,
+    Confirm,
+    Back,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 7] = [
+        InputAction::Up,
+        InputAction::Down,
+        InputAction::Left,
+        InputAction::Right,
+        InputAction::Pause,
+        InputAction::Confirm,
+        InputAction::Back,
+    ];
+
+    pub const STEERING: [InputAction; 4] = [
+        InputAction::Up,
+        InputAction::Down,
+        InputAction::Left,
+        InputAction::Right,
+    ];
+
+    // Read while a game runs.
+    fn in_game(self) -> bool {
+        !matches!(self, InputAction::Confirm | InputAction::Back)
+    }
+
+    // Read in the menus, the pause menu included.
+    fn in_menus(self) -> bool {
+        self != InputAction::Pause
+    }
+}
+
+// Something the controls menu can bind keys to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BindingTarget {
+    Action(InputAction),
+    // Steering key of one player in versus, keyboard only.
+    Versus(PlayerId, InputAction),
+}
+
+impl BindingTarget {
+    // Targets read at the same time can't share a key or button, pressing it would do both.
+    fn overlaps(self, other: BindingTarget) -> bool {
+        use BindingTarget::*;
+
+        if self == other {
+            return false;
+        }
+
+        match (self, other) {
+            (Action(a), Action(b)) => {
+                (a.in_game() && b.in_game()) || (a.in_menus() && b.in_menus())
+            }
+            // In versus the players' keys steer instead of the action keys, only pause is read too.
+            (Action(action), Versus(..)) | (Versus(..), Action(action)) => {
+                action == InputAction::Pause
+            }
+            (Versus(..), Versus(..)) => true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct Binding {
+    pub keys: Vec<KeyCode>,
+    pub buttons: Vec<GamepadButtonType>,
+}
+
+impl Binding {
+    fn new(keys: &[KeyCode], buttons: &[GamepadButtonType]) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            buttons: buttons.to_vec(),
+        }
+    }
+}
+
+// Which part of the gamepad steers the snake, menus always take both.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SteeringMode {
+    DPad,
+    Stick,
+    #[default]
+    Both,
+}
+
+impl SteeringMode {
+    pub fn next(self) -> Self {
+        match self {
+            SteeringMode::DPad => SteeringMode::Stick,
+            SteeringMode::Stick => SteeringMode::Both,
+            SteeringMode::Both => SteeringMode::DPad,
+        }
+    }
+
+    pub fn uses_dpad(self) -> bool {
+        self != SteeringMode::Stick
+    }
+
+    pub fn uses_stick(self) -> bool {
+        self != SteeringMode::DPad
+    }
+}
+
+// Stick push a direction needs to go past to count, and how far back it has to
+// come before the same direction counts again.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct StickSettings {
+    pub deadzone: f32,
+    pub release: f32,
+}
+
+impl Default for StickSettings {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.5,
+            release: 0.3,
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InputBindings {
+    pub actions: HashMap<InputAction, Binding>,
+    // Keys every player steers with in versus, one keyboard is shared.
+    #[serde(default = "InputBindings::default_versus_keys")]
+    pub versus_keys: [HashMap<InputAction, Vec<KeyCode>>; MAX_PLAYERS],
+    #[serde(default)]
+    pub steering: [SteeringMode; MAX_PLAYERS],
+    #[serde(default)]
+    pub stick: StickSettings,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GamepadButtonType as Button;
+
+        let actions = InputAction::ALL
+            .into_iter()
+            .map(|action| {
+                let binding = match action {
+                    InputAction::Up => Binding::new(&[KeyCode::Up, KeyCode::W], &[Button::DPadUp]),
+                    InputAction::Down => {
+                        Binding::new(&[KeyCode::Down, KeyCode::S], &[Button::DPadDown])
+                    }
+                    InputAction::Left => {
+                        Binding::new(&[KeyCode::Left, KeyCode::A], &[Button::DPadLeft])
+                    }
+                    InputAction::Right => {
+                        Binding::new(&[KeyCode::Right, KeyCode::D], &[Button::DPadRight])
+                    }
+                    InputAction::Pause => Binding::new(&[KeyCode::Escape], &[Button::Select]),
+                    InputAction::Confirm => {
+                        Binding::new(&[KeyCode::Return, KeyCode::Space], &[Button::South])
+                    }
+                    InputAction::Back => Binding::new(&[KeyCode::Escape], &[Button::East]),
+                };
+
+                (action, binding)
+            })
+            .collect();
+
+        Self {
+            actions,
+            versus_keys: Self::default_versus_keys(),
+            steering: Default::default(),
+            stick: StickSettings::default(),
+        }
+    }
+}
+
+impl InputBindings {
+    fn default_versus_keys() -> [HashMap<InputAction, Vec<KeyCode>>; MAX_PLAYERS] {
+        let keys = |up, down, left, right| {
+            HashMap::from([
+                (InputAction::Up, vec![up]),
+                (InputAction::Down, vec![down]),
+                (InputAction::Left, vec![left]),
+                (InputAction::Right, vec![right]),
+            ])
+        };
+
+        [
+            keys(KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D),
+            keys(KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right),
+        ]
+    }
+
+    pub fn get(&self, action: InputAction) -> Option<&Binding> {
+        self.actions.get(&action)
+    }
+
+    pub fn keys(&self, target: BindingTarget) -> &[KeyCode] {
+        let keys = match target {
+            BindingTarget::Action(action) => self.get(action).map(|binding| &binding.keys),
+            BindingTarget::Versus(player, action) => self.versus_keys[player.0].get(&action),
+        };
+
+        keys.map_or(&[], |keys| keys.as_slice())
+    }
+
+    fn targets() -> impl Iterator<Item = BindingTarget> {
+        let actions = InputAction::ALL.into_iter().map(BindingTarget::Action);
+        let versus = PlayerId::all(MAX_PLAYERS).flat_map(|player| {
+            InputAction::STEERING
+                .into_iter()
+                .map(move |action| BindingTarget::Versus(player, action))
+        });
+
+        actions.chain(versus)
+    }
+
+    // Another target that already reads `key` at the same time as `target`.
+    pub fn key_conflict(&self, target: BindingTarget, key: KeyCode) -> Option<BindingTarget> {
+        Self::targets().find(|other| target.overlaps(*other) && self.keys(*other).contains(&key))
+    }
+
+    pub fn button_conflict(
+        &self,
+        action: InputAction,
+        button: GamepadButtonType,
+    ) -> Option<BindingTarget> {
+        InputAction::ALL
+            .into_iter()
+            .map(BindingTarget::Action)
+            .filter(|other| BindingTarget::Action(action).overlaps(*other))
+            .find(|other| match other {
+                BindingTarget::Action(other) => self
+                    .get(*other)
+                    .is_some_and(|binding| binding.buttons.contains(&button)),
+                BindingTarget::Versus(..) => false,
+            })
+    }
+
+    // Adds `key` next to the ones already bound.
+    pub fn add_key(&mut self, target: BindingTarget, key: KeyCode) {
+        let keys = match target {
+            BindingTarget::Action(action) => &mut self.actions.entry(action).or_default().keys,
+            BindingTarget::Versus(player, action) => {
+                self.versus_keys[player.0].entry(action).or_default()
+            }
+        };
+
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    pub fn add_button(&mut self, action: InputAction, button: GamepadButtonType) {
+        let buttons = &mut self.actions.entry(action).or_default().buttons;
+
+        if !buttons.contains(&button) {
+            buttons.push(button);
+        }
+    }
+
+    // Unbinds every key and button of `target`.
+    pub fn clear(&mut self, target: BindingTarget) {
+        match target {
+            BindingTarget::Action(action) => {
+                self.actions.insert(action, Binding::default());
+            }
+            BindingTarget::Versus(player, action) => {
+                self.versus_keys[player.0].insert(action, Vec::new());
+            }
+        }
+    }
+
+    fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(BINDINGS_FILE))
+    }
+
+    // Missing or broken files fall back to the default bindings.
+    pub fn load() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            return Self::default();
+        }
+
+        let Some(text) = Self::path().and_then(|path| fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        ron::from_str(&text).unwrap_or_else(|err| {
+            error!("Failed to read input bindings: {err}");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) {
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+
+        let Some(path) = Self::path() else {
+            warn!("Input bindings won't be saved: no data directory");
+            return;
+        };
+
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+                }
+
+                fs::write(&path, text).map_err(|err| err.to_string())
+            });
+
+        if let Err(err) = result {
+            error!("Failed to save input bindings: {err}");
+        }
+    }
+}
+
+// Set while the controls menu waits for a new key or button for `target`.
+// No action reads as pressed meanwhile.
+#[derive(Resource, Default)]
+pub struct BindingCapture {
+    pub target: Option<BindingTarget>,
+    // Owner of the last key or button pressed during the capture, it wasn't bound.
+    pub conflict: Option<BindingTarget>,
+}
+
+// Keys that type text, skipped by `non_text_key_just_pressed`.
+fn is_text_key(key: KeyCode) -> bool {
+    use KeyCode::*;
+
+    matches!(
+        key,
+        A | B
+            | C
+            | D
+            | E
+            | F
+            | G
+            | H
+            | I
+            | J
+            | K
+            | L
+            | M
+            | N
+            | O
+            | P
+            | Q
+            | R
+            | S
+            | T
+            | U
+            | V
+            | W
+            | X
+            | Y
+            | Z
+            | Key0
+            | Key1
+            | Key2
+            | Key3
+            | Key4
+            | Key5
+            | Key6
+            | Key7
+            | Key8
+            | Key9
+            | Space
+    )
+}
+
+// Reads actions through the current `InputBindings`.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, InputBindings>,
+    capture: Res<'w, BindingCapture>,
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    button_inputs: Res<'w, Input<GamepadButton>>,
+    gamepads: Res<'w, Gamepads>,
+}
+
+impl ActionInput<'_> {
+    fn keys(&self, action: InputAction) -> impl Iterator<Item = &KeyCode> {
+        self.bindings
+            .get(action)
+            .into_iter()
+            .flat_map(|binding| binding.keys.iter())
+            .filter(|_| self.capture.target.is_none())
+    }
+
+    fn buttons(&self, action: InputAction) -> impl Iterator<Item = &GamepadButtonType> {
+        self.bindings
+            .get(action)
+            .into_iter()
+            .flat_map(|binding| binding.buttons.iter())
+            .filter(|_| self.capture.target.is_none())
+    }
+
+    pub fn key_just_pressed(&self, action: InputAction) -> bool {
+        self.keys(action)
+            .any(|key| self.keyboard_input.just_pressed(*key))
+    }
+
+    // For when a text field is shown and letters should be typed instead.
+    pub fn non_text_key_just_pressed(&self, action: InputAction) -> bool {
+        self.keys(action)
+            .any(|key| !is_text_key(*key) && self.keyboard_input.just_pressed(*key))
+    }
+
+    pub fn versus_key_just_pressed(&self, player: PlayerId, action: InputAction) -> bool {
+        self.capture.target.is_none()
+            && self.bindings.versus_keys[player.0]
+                .get(&action)
+                .into_iter()
+                .flatten()
+                .any(|key| self.keyboard_input.just_pressed(*key))
+    }
+
+    pub fn button_just_pressed(&self, action: InputAction, gamepad: Gamepad) -> bool {
+        self.buttons(action).any(|button_type| {
+            self.button_inputs
+                .just_pressed(GamepadButton::new(gamepad, *button_type))
+        })
+    }
+
+    pub fn any_button_just_pressed(&self, action: InputAction) -> bool {
+        self.gamepads
+            .iter()
+            .any(|gamepad| self.button_just_pressed(action, gamepad))
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.key_just_pressed(action) || self.any_button_just_pressed(action)
+    }
+
+    pub fn bindings(&self) -> &InputBindings {
+        &self.bindings
+    }
+}
+
+pub struct InputBindingsPlugin;
+
+impl Plugin for InputBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputBindings::load())
+            .insert_resource(BindingCapture::default());
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+};
+
+use crate::libs::{
+    game_configuration::GameConfiguration, game_states::GameState, player::MAX_PLAYERS,
+    replay::playback::ReplayPlayback, schedule::InGameSet,
+};
+
+use super::action_events::ActionPauseEvent;
+
+// Gamepad each player steers with, indexed by player.
+// Gamepads are handed to the first player without one as they connect.
+#[derive(Resource)]
+pub struct PlayerDevices {
+    pub players: Vec<Option<Gamepad>>,
+}
+
+impl Default for PlayerDevices {
+    fn default() -> Self {
+        Self {
+            players: vec![None; MAX_PLAYERS],
+        }
+    }
+}
+
+impl PlayerDevices {
+    pub fn get(&self, player: usize) -> Option<Gamepad> {
+        self.players.get(player).copied().flatten()
+    }
+
+    pub fn player_of(&self, gamepad: Gamepad) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|device| *device == Some(gamepad))
+    }
+
+    // Player the gamepad went to, `None` when every player already has one.
+    pub fn assign(&mut self, gamepad: Gamepad) -> Option<usize> {
+        if let Some(player) = self.player_of(gamepad) {
+            return Some(player);
+        }
+
+        let player = self.players.iter().position(Option::is_none)?;
+        self.players[player] = Some(gamepad);
+
+        Some(player)
+    }
+
+    pub fn release(&mut self, gamepad: Gamepad) -> Option<usize> {
+        let player = self.player_of(gamepad)?;
+        self.players[player] = None;
+
+        Some(player)
+    }
+}
+
+// Players whose gamepad went away during the current game.
+#[derive(Resource, Default)]
+pub struct LostDevices(pub Vec<usize>);
+
+#[allow(clippy::too_many_arguments)]
+fn handle_connections(
+    mut ev_connection: EventReader<GamepadConnectionEvent>,
+    mut devices: ResMut<PlayerDevices>,
+    mut lost: ResMut<LostDevices>,
+    mut ev_action_pause: EventWriter<ActionPauseEvent>,
+    state: Res<State<GameState>>,
+    configuration: Res<GameConfiguration>,
+    time: Res<Time<Virtual>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    for ev in ev_connection.read() {
+        match &ev.connection {
+            GamepadConnection::Connected(info) => {
+                if let Some(player) = devices.assign(ev.gamepad) {
+                    info!("{} steers player {}", info.name, player + 1);
+                    lost.0.retain(|lost_player| *lost_player != player);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                let Some(player) = devices.release(ev.gamepad) else {
+                    continue;
+                };
+
+                // Nobody plays with the gamepads of players not in this game.
+                if *state.get() != GameState::InGame || player >= configuration.mode.player_count()
+                {
+                    continue;
+                }
+
+                // Replays pause on their own, the recorded game didn't lose a controller.
+                if !time.is_paused() && lost.0.is_empty() && playback.is_none() {
+                    ev_action_pause.send(ActionPauseEvent);
+                }
+
+                lost.0.push(player);
+            }
+        }
+    }
+}
+
+fn clear_lost_devices(mut lost: ResMut<LostDevices>) {
+    lost.0.clear();
+}
+
+pub struct InputDevicesPlugin;
+
+impl Plugin for InputDevicesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PlayerDevices::default())
+            .insert_resource(LostDevices::default())
+            .add_systems(Update, handle_connections.before(InGameSet::UserInput))
+            .add_systems(OnExit(GameState::InGame), clear_lost_devices);
+    }
+}
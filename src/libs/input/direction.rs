@@ -1,6 +1,7 @@
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default, Reflect, PartialEq, Clone, Copy, Debug)]
+#[derive(Default, Reflect, PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Direction {
     #[default]
     Up,
@@ -10,6 +11,13 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
     pub fn opposite(&self) -> Self {
         match &self {
             Direction::Up => Direction::Down,
@@ -18,4 +26,14 @@ impl Direction {
             Direction::Right => Direction::Left,
         }
     }
+
+    // The direction a quarter turn to the right.
+    pub fn clockwise(&self) -> Self {
+        match &self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
 }
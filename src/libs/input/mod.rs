@@ -1,3 +1,6 @@
 pub mod action_events;
+pub mod bindings;
+pub mod devices;
 pub mod direction;
 pub mod read_input;
+pub mod stick;
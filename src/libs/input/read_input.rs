@@ -1,86 +1,99 @@
-use crate::libs::schedule::InGameSet;
+use crate::libs::{
+    game_configuration::{GameConfiguration, GameMode},
+    player::{PlayerId, MAX_PLAYERS},
+    replay::playback::ReplayPlayback,
+    schedule::InGameSet,
+};
 
 use super::action_events::{ActionMoveEvent, ActionPauseEvent};
+use super::bindings::{ActionInput, InputAction};
+use super::devices::PlayerDevices;
 use super::direction::Direction;
+use super::stick::{left_stick, stick_direction};
 
 use bevy::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_user_input(
-    gamepads: Res<Gamepads>,
-    keyboard_input: Res<Input<KeyCode>>,
-    button_inputs: Res<Input<GamepadButton>>,
+    input: ActionInput,
+    devices: Res<PlayerDevices>,
+    axes: Res<Axis<GamepadAxis>>,
+    configuration: Res<GameConfiguration>,
     mut ev_action_move: EventWriter<ActionMoveEvent>,
     mut ev_action_pause: EventWriter<ActionPauseEvent>,
     time: Res<Time<Virtual>>,
+    // Direction every player's stick is held in since their last push.
+    mut held: Local<[Option<Direction>; MAX_PLAYERS]>,
 ) {
-    let gamepad = gamepads.iter().last();
-
-    let gamepad_up: bool = if let Some(gamepad) = gamepad {
-        button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadUp))
-    } else {
-        false
-    };
-    let keyboard_up =
-        keyboard_input.just_pressed(KeyCode::Up) || keyboard_input.just_pressed(KeyCode::W);
-
-    let gamepad_down: bool = if let Some(gamepad) = gamepad {
-        button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadDown))
-    } else {
-        false
-    };
-    let keyboard_down =
-        keyboard_input.just_pressed(KeyCode::Down) || keyboard_input.just_pressed(KeyCode::S);
-
-    let gamepad_left: bool = if let Some(gamepad) = gamepad {
-        button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadLeft))
-    } else {
-        false
-    };
-    let keyboard_left =
-        keyboard_input.just_pressed(KeyCode::Left) || keyboard_input.just_pressed(KeyCode::A);
-
-    let gamepad_right: bool = if let Some(gamepad) = gamepad {
-        button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadRight))
-    } else {
-        false
-    };
-    let keyboard_right =
-        keyboard_input.just_pressed(KeyCode::Right) || keyboard_input.just_pressed(KeyCode::D);
-
-    let gamepad_select: bool = if let Some(gamepad) = gamepad {
-        button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Select))
-    } else {
-        false
-    };
-    let keyboard_esc = keyboard_input.just_pressed(KeyCode::Escape);
-
-    // Do not produce move actions on pause.
-    if !time.is_paused() {
-        if gamepad_up || keyboard_up {
-            ev_action_move.send(ActionMoveEvent(Direction::Up));
-        }
+    let bindings = input.bindings();
+    let players = PlayerId::all(configuration.mode.player_count());
+    let mut pause = input.key_just_pressed(InputAction::Pause);
 
-        if gamepad_down || keyboard_down {
-            ev_action_move.send(ActionMoveEvent(Direction::Down));
-        }
+    for player in players {
+        let gamepad = devices.get(player.0);
+        let steering = bindings.steering[player.0];
+        let held = &mut held[player.0];
+
+        let button_pressed = |action: InputAction| {
+            gamepad.is_some_and(|gamepad| input.button_just_pressed(action, gamepad))
+        };
+
+        // Alone the player takes every key, in versus each one has their own.
+        let steered = |action: InputAction| {
+            let key_pressed = match configuration.mode {
+                GameMode::Single => input.key_just_pressed(action),
+                GameMode::Versus => input.versus_key_just_pressed(player, action),
+            };
+
+            key_pressed || steering.uses_dpad() && button_pressed(action)
+        };
 
-        if gamepad_left || keyboard_left {
-            ev_action_move.send(ActionMoveEvent(Direction::Left));
+        // Only a new push sends a move, holding the stick doesn't repeat it.
+        let mut pushed = None;
+
+        if let Some(gamepad) = gamepad
+            && steering.uses_stick()
+        {
+            let direction = stick_direction(*held, left_stick(&axes, gamepad), &bindings.stick);
+
+            if direction != *held {
+                pushed = direction;
+            }
+
+            *held = direction;
         }
 
-        if gamepad_right || keyboard_right {
-            ev_action_move.send(ActionMoveEvent(Direction::Right));
+        // Do not produce move actions on pause.
+        if !time.is_paused() {
+            for (action, direction) in [
+                (InputAction::Up, Direction::Up),
+                (InputAction::Down, Direction::Down),
+                (InputAction::Left, Direction::Left),
+                (InputAction::Right, Direction::Right),
+            ] {
+                if steered(action) || pushed == Some(direction) {
+                    ev_action_move.send(ActionMoveEvent { player, direction });
+                }
+            }
         }
+
+        pause |= button_pressed(InputAction::Pause);
     }
 
-    if gamepad_select || keyboard_esc {
+    if pause {
         ev_action_pause.send(ActionPauseEvent);
     }
 }
+
 pub struct ReadInputPlugin;
 
 impl Plugin for ReadInputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, get_user_input.in_set(InGameSet::UserInput));
+        app.add_systems(
+            Update,
+            get_user_input
+                .in_set(InGameSet::UserInput)
+                .run_if(not(resource_exists::<ReplayPlayback>())),
+        );
     }
 }
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use super::{bindings::StickSettings, direction::Direction};
+
+// Cardinal direction the stick points in the most, and how far.
+fn dominant_direction(stick: Vec2) -> (Direction, f32) {
+    if stick.x.abs() >= stick.y.abs() {
+        let direction = if stick.x > 0.0 {
+            Direction::Right
+        } else {
+            Direction::Left
+        };
+
+        (direction, stick.x.abs())
+    } else {
+        // Stick y grows upwards.
+        let direction = if stick.y > 0.0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        };
+
+        (direction, stick.y.abs())
+    }
+}
+
+fn push_towards(stick: Vec2, direction: Direction) -> f32 {
+    match direction {
+        Direction::Up => stick.y,
+        Direction::Down => -stick.y,
+        Direction::Left => -stick.x,
+        Direction::Right => stick.x,
+    }
+}
+
+// Direction the stick is held in, given the one it was held in before.
+// A direction is entered past the deadzone and kept until the stick falls back
+// under the release threshold, so a stick resting near the deadzone doesn't flicker.
+pub fn stick_direction(
+    held: Option<Direction>,
+    stick: Vec2,
+    settings: &StickSettings,
+) -> Option<Direction> {
+    let (dominant, push) = dominant_direction(stick);
+    let entered = (push >= settings.deadzone).then_some(dominant);
+
+    match held {
+        Some(held) if push_towards(stick, held) >= settings.release => entered.or(Some(held)),
+        _ => entered,
+    }
+}
+
+pub fn left_stick(axes: &Axis<GamepadAxis>, gamepad: Gamepad) -> Vec2 {
+    let axis = |axis_type| {
+        axes.get(GamepadAxis::new(gamepad, axis_type))
+            .unwrap_or_default()
+    };
+
+    Vec2::new(
+        axis(GamepadAxisType::LeftStickX),
+        axis(GamepadAxisType::LeftStickY),
+    )
+}
@@ -0,0 +1,191 @@
+use std::{fmt, path::Path};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{cell::Cell, input::direction::Direction};
+
+pub const LEVEL_FILES: [&str; 3] = ["levels/box.txt", "levels/cross.txt", "levels/rooms.txt"];
+
+// Levels are named after their file, e.g. `levels/box.txt` is `box`.
+pub fn level_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// ASCII level map, one character per cell:
+// `#` wall, `.` empty, `*` food spawn,
+// `^` `v` `<` `>` snake start facing up, down, left or right.
+#[derive(Asset, Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub walls: Vec<Cell>,
+    pub start: Cell,
+    pub start_direction: Direction,
+    // Food only spawns on these cells while any of them is free.
+    pub food_spawns: Vec<Cell>,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(std::io::Error),
+    Empty,
+    UnevenRows(u32),
+    UnknownTile(char, Cell),
+    SeveralStarts,
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::Io(err) => write!(f, "can't read level file: {err}"),
+            LevelError::Empty => write!(f, "level has no rows"),
+            LevelError::UnevenRows(row) => write!(f, "row {row} has a different width"),
+            LevelError::UnknownTile(tile, cell) => {
+                write!(f, "unknown tile '{tile}' at {}:{}", cell.x, cell.y)
+            }
+            LevelError::SeveralStarts => write!(f, "level has more than one snake start"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+impl Level {
+    pub fn parse(name: impl Into<String>, text: &str) -> Result<Self, LevelError> {
+        let rows: Vec<&str> = text
+            .lines()
+            .map(str::trim_end)
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let Some(first_row) = rows.first() else {
+            return Err(LevelError::Empty);
+        };
+
+        let width = first_row.chars().count() as u32;
+        let height = rows.len() as u32;
+
+        let mut walls: Vec<Cell> = Vec::new();
+        let mut food_spawns: Vec<Cell> = Vec::new();
+        let mut start: Option<(Cell, Direction)> = None;
+
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() as u32 != width {
+                return Err(LevelError::UnevenRows(y as u32));
+            }
+
+            for (x, tile) in row.chars().enumerate() {
+                let cell = Cell {
+                    x: x as u32,
+                    y: y as u32,
+                };
+
+                let start_direction = match tile {
+                    '.' => None,
+                    '#' => {
+                        walls.push(cell);
+                        None
+                    }
+                    '*' => {
+                        food_spawns.push(cell);
+                        None
+                    }
+                    '^' => Some(Direction::Up),
+                    'v' => Some(Direction::Down),
+                    '<' => Some(Direction::Left),
+                    '>' => Some(Direction::Right),
+                    _ => return Err(LevelError::UnknownTile(tile, cell)),
+                };
+
+                if let Some(direction) = start_direction {
+                    if start.is_some() {
+                        return Err(LevelError::SeveralStarts);
+                    }
+
+                    start = Some((cell, direction));
+                }
+            }
+        }
+
+        let (start, start_direction) = start.unwrap_or((
+            Cell {
+                x: width / 2,
+                y: height / 2,
+            },
+            Direction::Up,
+        ));
+
+        Ok(Self {
+            name: name.into(),
+            width,
+            height,
+            walls,
+            start,
+            start_direction,
+            food_spawns,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = LevelError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Level, LevelError>> {
+        Box::pin(async move {
+            let mut text = String::new();
+            reader
+                .read_to_string(&mut text)
+                .await
+                .map_err(LevelError::Io)?;
+
+            let name = level_name(&load_context.path().to_string_lossy());
+
+            Level::parse(name, &text)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["txt"]
+    }
+}
+
+// Handles in the same order as `LEVEL_FILES`.
+#[derive(Resource, Default)]
+pub struct LevelHandles(pub Vec<Handle<Level>>);
+
+fn load_levels(mut level_handles: ResMut<LevelHandles>, asset_server: Res<AssetServer>) {
+    level_handles.0 = LEVEL_FILES
+        .iter()
+        .map(|path| asset_server.load(*path))
+        .collect();
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .insert_resource(LevelHandles::default())
+            .add_systems(Startup, load_levels);
+    }
+}
@@ -1,16 +1,27 @@
 pub mod animation;
+pub mod attract;
 pub mod audio;
+pub mod board;
+pub mod bots;
 pub mod camera;
 pub mod cell;
+pub mod death;
 pub mod eatables;
 pub mod game_configuration;
+pub mod game_rng;
 pub mod game_states;
 pub mod globals;
+pub mod high_scores;
 pub mod input;
+pub mod level;
 pub mod particles;
 pub mod pause;
+pub mod player;
+pub mod replay;
 pub mod schedule;
 pub mod score;
+pub mod sim;
 pub mod snake;
+pub mod snake_body;
 pub mod ui;
 pub mod utils;
@@ -3,11 +3,21 @@ use bevy_particle_systems::{
     JitteredValue, ParticleBurst, ParticleSystem, ParticleSystemBundle, Playing, VelocityModifier,
 };
 
-use super::{eatables::eat_event::EatEvent, schedule::InGameSet, utils::grid_to_screen};
+use super::{
+    eatables::{eat_event::EatEvent, powerups::registry::PowerupRegistry},
+    game_configuration::GameConfiguration,
+    schedule::InGameSet,
+    utils::grid_to_screen,
+};
 
-fn spawn_particle_on_eat(mut ev_eat: EventReader<EatEvent>, mut commands: Commands) {
+fn spawn_particle_on_eat(
+    mut ev_eat: EventReader<EatEvent>,
+    mut commands: Commands,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
+) {
     for ev in ev_eat.read() {
-        let world_pos = grid_to_screen(ev.pos.x, ev.pos.y);
+        let world_pos = grid_to_screen(ev.pos.x, ev.pos.y, &configuration);
 
         commands.spawn((
             ParticleSystemBundle {
@@ -23,7 +33,7 @@ fn spawn_particle_on_eat(mut ev_eat: EventReader<EatEvent>, mut commands: Comman
                     lifetime: JitteredValue::new(0.4),
                     scale: 2.0.into(),
                     velocity_modifiers: vec![VelocityModifier::Drag(0.001.into())],
-                    color: ev.food.0.get_color().into(),
+                    color: registry.entry(&ev.food.0).get_color().into(),
                     bursts: vec![ParticleBurst {
                         time: 0.0,
                         count: 50,
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    game_configuration::GameConfiguration,
+    globals::{
+        BOT_HEAD_COLOR, BOT_TAIL_COLORS, HEAD_COLOR, PLAYER_TWO_HEAD_COLOR, PLAYER_TWO_TAIL_COLOR,
+        TAIL_COLOR,
+    },
+};
+
+// Humans playing at once.
+pub const MAX_PLAYERS: usize = 2;
+pub const MAX_BOTS: usize = 3;
+pub const MAX_SNAKES: usize = MAX_PLAYERS + MAX_BOTS;
+
+// Which player a snake, its input and its score belong to.
+// Humans come first, bots take the ids after them.
+#[derive(
+    Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct PlayerId(pub usize);
+
+impl PlayerId {
+    pub const ONE: PlayerId = PlayerId(0);
+    pub const TWO: PlayerId = PlayerId(1);
+
+    // The first `count` players.
+    pub fn all(count: usize) -> impl Iterator<Item = PlayerId> {
+        (0..count.min(MAX_SNAKES)).map(PlayerId)
+    }
+
+    // Number shown to the players, starting at 1.
+    pub fn number(&self) -> usize {
+        self.0 + 1
+    }
+
+    // Bots are told apart by their tails.
+    pub fn head_color(&self, configuration: &GameConfiguration) -> Color {
+        match *self {
+            _ if configuration.is_bot(*self) => BOT_HEAD_COLOR,
+            PlayerId::TWO => PLAYER_TWO_HEAD_COLOR,
+            _ => HEAD_COLOR,
+        }
+    }
+
+    pub fn tail_color(&self, configuration: &GameConfiguration) -> Color {
+        match *self {
+            _ if configuration.is_bot(*self) => {
+                let bot = self.0 - configuration.mode.player_count();
+                BOT_TAIL_COLORS[bot % BOT_TAIL_COLORS.len()]
+            }
+            PlayerId::TWO => PLAYER_TWO_TAIL_COLOR,
+            _ => TAIL_COLOR,
+        }
+    }
+}
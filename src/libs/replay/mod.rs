@@ -0,0 +1,4 @@
+pub mod playback;
+pub mod recorder;
+pub mod replay_file;
+pub mod replay_plugin;
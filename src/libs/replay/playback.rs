@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::libs::{
+    eatables::powerups::registry::{update_powerup_registry, PowerupRegistry},
+    game_configuration::GameConfiguration,
+    game_states::GameState,
+    input::action_events::{ActionMoveEvent, ActionPauseEvent},
+    schedule::InGameSet,
+    utils::cli_arg,
+};
+
+use super::replay_file::{Replay, ReplayAction};
+
+pub const FAST_FORWARD_SPEED: f32 = 4.0;
+
+// While this resource exists the game is driven by the replay instead of the player.
+// Space toggles frame-step mode, Right steps one tick, holding F fast-forwards.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub stepping: bool,
+    next_entry: usize,
+    previous_seed: Option<u64>,
+    previous_powerups: PowerupRegistry,
+}
+
+impl ReplayPlayback {
+    pub fn start(
+        replay: Replay,
+        configuration: &mut GameConfiguration,
+        registry: &mut PowerupRegistry,
+    ) -> Self {
+        let previous_seed = configuration.seed;
+        replay.apply_to(configuration);
+        let previous_powerups = std::mem::replace(registry, replay.powerups.clone());
+
+        Self {
+            replay,
+            stepping: false,
+            next_entry: 0,
+            previous_seed,
+            previous_powerups,
+        }
+    }
+}
+
+fn start_playback_from_args(
+    mut commands: Commands,
+    mut configuration: ResMut<GameConfiguration>,
+    mut registry: ResMut<PowerupRegistry>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(path) = cli_arg("--replay") else {
+        return;
+    };
+
+    match Replay::load(&path) {
+        Ok(replay) => {
+            commands.insert_resource(ReplayPlayback::start(
+                replay,
+                &mut configuration,
+                &mut registry,
+            ));
+            next_state.set(GameState::InGame);
+        }
+        Err(err) => error!("Failed to load replay {path}: {err}"),
+    }
+}
+
+fn advance_playback_timer(
+    mut configuration: ResMut<GameConfiguration>,
+    mut playback: ResMut<ReplayPlayback>,
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        playback.stepping = !playback.stepping;
+    }
+
+    let remaining = configuration.tick_timer.remaining();
+
+    let delta = if playback.stepping {
+        if keyboard_input.just_pressed(KeyCode::Right) {
+            remaining
+        } else {
+            Duration::ZERO
+        }
+    } else if keyboard_input.pressed(KeyCode::F) {
+        time.delta().mul_f32(FAST_FORWARD_SPEED)
+    } else {
+        time.delta()
+    };
+
+    // Never finish more than one tick per frame, the snake only moves once per frame.
+    configuration.advance(delta.min(remaining));
+}
+
+fn feed_playback_actions(
+    mut playback: ResMut<ReplayPlayback>,
+    configuration: Res<GameConfiguration>,
+    mut ev_action_move: EventWriter<ActionMoveEvent>,
+    mut ev_action_pause: EventWriter<ActionPauseEvent>,
+) {
+    let tick = configuration.upcoming_tick();
+
+    while let Some(entry) = playback.replay.entries.get(playback.next_entry).copied() {
+        if entry.tick > tick {
+            break;
+        }
+
+        match entry.action {
+            ReplayAction::Move(player, direction) => {
+                ev_action_move.send(ActionMoveEvent { player, direction })
+            }
+            ReplayAction::Pause => ev_action_pause.send(ActionPauseEvent),
+        }
+
+        playback.next_entry += 1;
+    }
+}
+
+// `powerups.ron` can finish loading or be edited during playback,
+// the recorded powerups stay in place and the new ones are given back when playback stops.
+fn keep_replay_powerups(
+    mut playback: ResMut<ReplayPlayback>,
+    mut registry: ResMut<PowerupRegistry>,
+) {
+    if *registry != playback.replay.powerups {
+        let recorded = playback.replay.powerups.clone();
+        playback.previous_powerups = std::mem::replace(&mut *registry, recorded);
+    }
+}
+
+fn stop_playback(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    mut configuration: ResMut<GameConfiguration>,
+    mut registry: ResMut<PowerupRegistry>,
+) {
+    if let Some(playback) = playback {
+        configuration.seed = playback.previous_seed;
+        *registry = playback.previous_powerups.clone();
+        commands.remove_resource::<ReplayPlayback>();
+    }
+}
+
+pub struct ReplayPlaybackPlugin;
+
+impl Plugin for ReplayPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_playback_from_args)
+            .add_systems(
+                Update,
+                keep_replay_powerups
+                    .after(update_powerup_registry)
+                    .before(InGameSet::DespawnEntities)
+                    .run_if(resource_exists::<ReplayPlayback>()),
+            )
+            .add_systems(
+                Update,
+                advance_playback_timer
+                    .in_set(InGameSet::DespawnEntities)
+                    .run_if(resource_exists::<ReplayPlayback>()),
+            )
+            .add_systems(
+                Update,
+                feed_playback_actions
+                    .in_set(InGameSet::UserInput)
+                    .run_if(resource_exists::<ReplayPlayback>()),
+            )
+            .add_systems(OnExit(GameState::InGame), stop_playback);
+    }
+}
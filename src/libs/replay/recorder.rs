@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::libs::{
+    eatables::powerups::registry::PowerupRegistry,
+    game_configuration::GameConfiguration,
+    game_rng::{reseed_game_rng, GameRng},
+    game_states::GameState,
+    input::action_events::{ActionMoveEvent, ActionPauseEvent},
+    schedule::InGameSet,
+    utils::cli_arg,
+};
+
+use super::replay_file::{Replay, ReplayAction, ReplayEntry};
+
+// Keeps the replay of the current (or last) game.
+// It is written to `path` when the game ends, if one was given with `--record <path>`.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub replay: Replay,
+    pub path: Option<PathBuf>,
+}
+
+impl Default for ReplayRecorder {
+    fn default() -> Self {
+        Self {
+            replay: Replay::new(
+                0,
+                &GameConfiguration::default(),
+                &PowerupRegistry::default(),
+            ),
+            path: cli_arg("--record").map(PathBuf::from),
+        }
+    }
+}
+
+fn start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    rng: Res<GameRng>,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
+) {
+    recorder.replay = Replay::new(rng.seed(), &configuration, &registry);
+}
+
+fn record_actions(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut ev_move: EventReader<ActionMoveEvent>,
+    mut ev_pause: EventReader<ActionPauseEvent>,
+    configuration: Res<GameConfiguration>,
+) {
+    let tick = configuration.upcoming_tick();
+
+    for ev in ev_move.read() {
+        recorder.replay.entries.push(ReplayEntry {
+            tick,
+            action: ReplayAction::Move(ev.player, ev.direction),
+        });
+    }
+
+    for _ in ev_pause.read() {
+        recorder.replay.entries.push(ReplayEntry {
+            tick,
+            action: ReplayAction::Pause,
+        });
+    }
+}
+
+fn save_recording(recorder: Res<ReplayRecorder>) {
+    if let Some(path) = &recorder.path {
+        match recorder.replay.save(path) {
+            Ok(()) => info!("Replay saved to {}", path.display()),
+            Err(err) => error!("Failed to save replay to {}: {err}", path.display()),
+        }
+    }
+}
+
+pub struct ReplayRecorderPlugin;
+
+impl Plugin for ReplayRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReplayRecorder::default())
+            .add_systems(
+                OnEnter(GameState::InGame),
+                start_recording.after(reseed_game_rng),
+            )
+            // Runs after `InGameSet::UserInput` so actions are recorded on the frame they are made.
+            .add_systems(Update, record_actions.in_set(InGameSet::EntityUpdates))
+            .add_systems(OnEnter(GameState::FinishMenu), save_recording);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::libs::{
+    eatables::powerups::registry::PowerupRegistry,
+    game_configuration::{BoundaryMode, GameConfiguration, GameDifficulty, GameMode},
+    input::direction::Direction,
+    level::Level,
+    player::PlayerId,
+    sim::bot::BotLevel,
+};
+
+// Bump on any change to the file layout or to the game rules once a version has shipped,
+// old replays would desync otherwise.
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ReplayAction {
+    Move(PlayerId, Direction),
+    Pause,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ReplayEntry {
+    // Tick of the snake move the action was applied before.
+    pub tick: u64,
+    pub action: ReplayAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub difficulty: GameDifficulty,
+    pub grid_width: u32,
+    pub grid_height: u32,
+    pub cell_size: f32,
+    pub boundary_mode: BoundaryMode,
+    pub mode: GameMode,
+    pub bots: usize,
+    pub bot_level: BotLevel,
+    // The whole level is stored, so the replay doesn't depend on level files.
+    pub level: Option<Level>,
+    // Same for the powerups, so later edits to `powerups.ron` don't change the game.
+    pub powerups: PowerupRegistry,
+    pub entries: Vec<ReplayEntry>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Format(String),
+    Version(u32),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "can't access replay file: {err}"),
+            ReplayError::Format(err) => write!(f, "malformed replay file: {err}"),
+            ReplayError::Version(version) => write!(
+                f,
+                "replay version {version} is not supported, expected {REPLAY_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    pub fn new(seed: u64, configuration: &GameConfiguration, registry: &PowerupRegistry) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            difficulty: configuration.current_difficulty,
+            grid_width: configuration.grid_width,
+            grid_height: configuration.grid_height,
+            cell_size: configuration.cell_size,
+            boundary_mode: configuration.boundary_mode,
+            mode: configuration.mode,
+            bots: configuration.bots,
+            bot_level: configuration.bot_level,
+            level: configuration.level.clone(),
+            powerups: registry.clone(),
+            entries: Vec::new(),
+        }
+    }
+
+    // Puts the configuration in the state the recorded game was started with.
+    pub fn apply_to(&self, configuration: &mut GameConfiguration) {
+        configuration.seed = Some(self.seed);
+        configuration.set_difficulty_and_reset_timer(self.difficulty);
+        configuration.set_grid(self.grid_width, self.grid_height, self.cell_size);
+        configuration.boundary_mode = self.boundary_mode;
+        configuration.mode = self.mode;
+        configuration.bots = self.bots;
+        configuration.bot_level = self.bot_level;
+
+        if let Some(level) = &self.level {
+            configuration.set_level(level.clone());
+        }
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ReplayError> {
+        let replay: Replay =
+            ron::from_str(text).map_err(|err| ReplayError::Format(err.to_string()))?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::Version(replay.version));
+        }
+
+        Ok(replay)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ReplayError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| ReplayError::Format(err.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let text = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        Self::from_ron(&text)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        fs::write(path, self.to_ron()?).map_err(ReplayError::Io)
+    }
+}
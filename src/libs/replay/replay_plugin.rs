@@ -0,0 +1,13 @@
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+
+use super::{playback::ReplayPlaybackPlugin, recorder::ReplayRecorderPlugin};
+
+pub struct ReplayPlugins;
+
+impl PluginGroup for ReplayPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(ReplayRecorderPlugin)
+            .add(ReplayPlaybackPlugin)
+    }
+}
@@ -1,13 +1,39 @@
 use bevy::prelude::*;
 
-use super::{eatables::eat_event::EatEvent, game_states::GameState, schedule::InGameSet};
+use super::{
+    eatables::{
+        eat_event::{send_eat_event, EatEvent},
+        powerups::{active_effects::ActiveEffects, registry::PowerupRegistry},
+    },
+    game_states::GameState,
+    player::{PlayerId, MAX_SNAKES},
+    schedule::InGameSet,
+};
 
+// Points of every snake in the current (or last) game, bots included.
 #[derive(Resource, Default)]
-pub struct Score(pub u32);
+pub struct Score(pub [u32; MAX_SNAKES]);
 
-fn grow_score_on_eat(mut ev_eat: EventReader<EatEvent>, mut score: ResMut<Score>) {
-    for _ in ev_eat.read() {
-        score.0 += 1;
+impl Score {
+    pub fn get(&self, player: PlayerId) -> u32 {
+        self.0[player.0]
+    }
+}
+
+fn grow_score_on_eat(
+    mut ev_eat: EventReader<EatEvent>,
+    mut score: ResMut<Score>,
+    snake_effects: Query<(&ActiveEffects, &PlayerId)>,
+    registry: Res<PowerupRegistry>,
+) {
+    for ev in ev_eat.read() {
+        // Only the eater's own multipliers count.
+        let multiplier = snake_effects
+            .iter()
+            .find(|(_, player)| **player == ev.player)
+            .map_or(1, |(effects, _)| effects.score_multiplier(&registry));
+
+        score.0[ev.player.0] += multiplier;
     }
 }
 
@@ -22,8 +48,11 @@ impl Plugin for ScorePlugin {
         app.insert_resource(Score::default())
             .add_systems(
                 Update,
-                grow_score_on_eat.in_set(InGameSet::CollisionDetection),
+                // Same frame as the eat, before the eaten powerup's own effect starts.
+                grow_score_on_eat
+                    .after(send_eat_event)
+                    .in_set(InGameSet::CollisionDetection),
             )
-            .add_systems(OnExit(GameState::FinishMenu), reset_score);
+            .add_systems(OnEnter(GameState::InGame), reset_score);
     }
 }
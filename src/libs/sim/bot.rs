@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use bevy::reflect::Reflect;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::libs::{cell::Cell, game_configuration::BoundaryMode, input::direction::Direction};
+
+use super::{rules, snake_sim::SnakeSim};
+
+// How hard a CPU snake tries.
+#[derive(Default, Reflect, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BotLevel {
+    // Any move that doesn't die right away.
+    Random,
+    // The safe move that gets closest to the nearest food.
+    #[default]
+    Greedy,
+    // Shortest path to a food, or the roomiest move when no food can be reached.
+    Pathfinding,
+}
+
+impl BotLevel {
+    pub fn next(self) -> Self {
+        match self {
+            BotLevel::Random => BotLevel::Greedy,
+            BotLevel::Greedy => BotLevel::Pathfinding,
+            BotLevel::Pathfinding => BotLevel::Random,
+        }
+    }
+}
+
+// What a bot knows about the board at the start of a tick.
+#[derive(Clone, Debug)]
+pub struct BotView {
+    pub width: u32,
+    pub height: u32,
+    pub boundary: BoundaryMode,
+    pub foods: Vec<Cell>,
+    blocked: Vec<bool>,
+}
+
+impl BotView {
+    pub fn new(width: u32, height: u32, boundary: BoundaryMode) -> Self {
+        Self {
+            width,
+            height,
+            boundary,
+            foods: Vec::new(),
+            blocked: vec![false; (width * height) as usize],
+        }
+    }
+
+    // Board of a headless game, as seen by its snake.
+    pub fn from_sim(sim: &SnakeSim) -> Self {
+        let mut view = Self::new(sim.width, sim.height, sim.boundary_mode);
+        view.block(sim.walls.iter().copied());
+        view.block(sim.body.iter().copied());
+        view.foods = sim.foods.iter().map(|food| food.pos).collect();
+
+        view
+    }
+
+    fn index(&self, cell: Cell) -> usize {
+        (cell.y * self.width + cell.x) as usize
+    }
+
+    // Marks walls and snake parts, nothing moves onto them.
+    pub fn block(&mut self, cells: impl IntoIterator<Item = Cell>) {
+        for cell in cells {
+            let id = self.index(cell);
+
+            if let Some(blocked) = self.blocked.get_mut(id) {
+                *blocked = true;
+            }
+        }
+    }
+
+    pub fn is_free(&self, cell: Cell) -> bool {
+        !self.blocked[self.index(cell)]
+    }
+
+    // Cell a step in `direction` leads to, `None` if it kills the snake.
+    fn step(&self, cell: Cell, direction: Direction) -> Option<Cell> {
+        rules::move_cell(cell, direction, self.width, self.height, self.boundary)
+            .filter(|next| self.is_free(*next))
+    }
+
+    fn safe_moves(&self, head: Cell, direction: Direction) -> Vec<(Direction, Cell)> {
+        Direction::ALL
+            .into_iter()
+            .filter(|next| *next != direction.opposite())
+            .filter_map(|next| self.step(head, next).map(|cell| (next, cell)))
+            .collect()
+    }
+
+    fn distance(&self, a: Cell, b: Cell) -> u32 {
+        let dx = a.x.abs_diff(b.x);
+        let dy = a.y.abs_diff(b.y);
+
+        match self.boundary {
+            BoundaryMode::Wrap => dx.min(self.width - dx) + dy.min(self.height - dy),
+            BoundaryMode::Solid => dx + dy,
+        }
+    }
+
+    // Free cells reachable from `start`, `start` included.
+    fn room(&self, start: Cell) -> usize {
+        let mut seen = vec![false; self.blocked.len()];
+        let mut queue = VecDeque::from([start]);
+        seen[self.index(start)] = true;
+        let mut count = 0;
+
+        while let Some(cell) = queue.pop_front() {
+            count += 1;
+
+            for direction in Direction::ALL {
+                if let Some(next) = self.step(cell, direction)
+                    && !seen[self.index(next)]
+                {
+                    seen[self.index(next)] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        count
+    }
+
+    // First move of the shortest path from `head` to any food.
+    fn path_to_food(&self, head: Cell, direction: Direction) -> Option<Direction> {
+        let mut seen = vec![false; self.blocked.len()];
+        let mut queue: VecDeque<(Cell, Direction)> = VecDeque::new();
+
+        for (first, cell) in self.safe_moves(head, direction) {
+            seen[self.index(cell)] = true;
+            queue.push_back((cell, first));
+        }
+
+        while let Some((cell, first)) = queue.pop_front() {
+            if self.foods.contains(&cell) {
+                return Some(first);
+            }
+
+            for direction in Direction::ALL {
+                if let Some(next) = self.step(cell, direction)
+                    && !seen[self.index(next)]
+                {
+                    seen[self.index(next)] = true;
+                    queue.push_back((next, first));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// Direction a bot at `head` moving in `direction` picks for the next tick.
+// Keeps going straight when every move is deadly.
+pub fn choose_direction(
+    level: BotLevel,
+    view: &BotView,
+    head: Cell,
+    direction: Direction,
+    rng: &mut impl Rng,
+) -> Direction {
+    let moves = view.safe_moves(head, direction);
+
+    let random = |rng: &mut _| {
+        moves
+            .choose(rng)
+            .map_or(direction, |(direction, _)| *direction)
+    };
+
+    match level {
+        BotLevel::Random => random(rng),
+        BotLevel::Greedy => {
+            let food_distance = |cell: Cell| {
+                view.foods
+                    .iter()
+                    .map(|food| view.distance(cell, *food))
+                    .min()
+            };
+
+            // Going straight wins ties, so the bot doesn't zigzag.
+            moves
+                .iter()
+                .filter_map(|(next, cell)| {
+                    food_distance(*cell).map(|distance| (*next, (distance, *next != direction)))
+                })
+                .min_by_key(|(_, key)| *key)
+                .map_or_else(|| random(rng), |(next, _)| next)
+        }
+        BotLevel::Pathfinding => view.path_to_food(head, direction).unwrap_or_else(|| {
+            moves
+                .iter()
+                .max_by_key(|(_, cell)| view.room(*cell))
+                .map_or(direction, |(next, _)| *next)
+        }),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod bot;
+pub mod rules;
+pub mod sim_event;
+pub mod snake_env;
+pub mod snake_sim;
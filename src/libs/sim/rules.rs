@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::libs::{
+    cell::Cell,
+    eatables::powerups::{
+        powerup::Powerup,
+        registry::{PowerupEntry, PowerupRegistry},
+    },
+    game_configuration::BoundaryMode,
+    input::direction::Direction,
+};
+
+// Queues a direction change, ignoring repeats of the last planned direction.
+pub fn plan_direction(planned: &mut VecDeque<Direction>, direction: Direction) {
+    if planned.back() != Some(&direction) {
+        planned.push_back(direction);
+    }
+}
+
+// Pops the next planned direction and returns the direction the head moves in.
+// Reversing into the body is not allowed and drops the rest of the plan.
+pub fn next_direction(current: Direction, planned: &mut VecDeque<Direction>) -> Direction {
+    match planned.pop_front() {
+        Some(plan_dir) if current != plan_dir.opposite() => plan_dir,
+        Some(_) => {
+            planned.clear();
+            current
+        }
+        None => current,
+    }
+}
+
+// Moves a cell one step in `direction`, wrapping around the board edges.
+pub fn step_cell(cell: Cell, direction: Direction, width: u32, height: u32) -> Cell {
+    let mut cell = cell;
+
+    match direction {
+        Direction::Up => {
+            if cell.y == 0 {
+                cell.y = height - 1;
+            } else {
+                cell.y -= 1;
+            }
+        }
+        Direction::Down => {
+            if cell.y == height - 1 {
+                cell.y = 0;
+            } else {
+                cell.y += 1;
+            }
+        }
+        Direction::Left => {
+            if cell.x == 0 {
+                cell.x = width - 1;
+            } else {
+                cell.x -= 1;
+            }
+        }
+        Direction::Right => {
+            if cell.x == width - 1 {
+                cell.x = 0;
+            } else {
+                cell.x += 1;
+            }
+        }
+    }
+
+    cell
+}
+
+// Direction of the step from `from` to `to`, counting wrapping around an edge as a step.
+// Returns `None` if the cells aren't neighbours.
+pub fn neighbour_direction(from: Cell, to: Cell, width: u32, height: u32) -> Option<Direction> {
+    Direction::ALL
+        .into_iter()
+        .find(|direction| from != to && step_cell(from, *direction, width, height) == to)
+}
+
+// Checks whether a step in `direction` would leave the board.
+pub fn leaves_board(cell: Cell, direction: Direction, width: u32, height: u32) -> bool {
+    match direction {
+        Direction::Up => cell.y == 0,
+        Direction::Down => cell.y == height - 1,
+        Direction::Left => cell.x == 0,
+        Direction::Right => cell.x == width - 1,
+    }
+}
+
+// Moves a cell one step in `direction` following the board's `boundary`.
+// Returns `None` if the cell hits a solid edge.
+pub fn move_cell(
+    cell: Cell,
+    direction: Direction,
+    width: u32,
+    height: u32,
+    boundary: BoundaryMode,
+) -> Option<Cell> {
+    if boundary == BoundaryMode::Solid && leaves_board(cell, direction, width, height) {
+        None
+    } else {
+        Some(step_cell(cell, direction, width, height))
+    }
+}
+
+// Seconds per tick for the difficulty's `base` rate scaled by every active speed effect.
+pub fn tick_rate(base: f32, factors: impl IntoIterator<Item = f32>) -> f32 {
+    factors.into_iter().fold(base, |rate, factor| rate * factor)
+}
+
+// Points for one eaten food. Multipliers don't stack, the largest running one counts.
+pub fn score_multiplier(multipliers: impl IntoIterator<Item = u32>) -> u32 {
+    multipliers.into_iter().max().unwrap_or(1)
+}
+
+// Checks whether eating `entry` adds a tail part. Shorten takes parts away instead.
+pub fn grows(entry: &PowerupEntry) -> bool {
+    entry.effect != Powerup::Shorten
+}
+
+// Tail parts eating `entry` removes from a snake of `length` parts, the head always stays.
+pub fn tails_removed(entry: &PowerupEntry, length: usize) -> u32 {
+    if entry.effect == Powerup::Shorten {
+        (entry.magnitude as u32).min(length.saturating_sub(1) as u32)
+    } else {
+        0
+    }
+}
+
+// Extra foods eating `entry` spawns.
+pub fn feast_foods(entry: &PowerupEntry) -> u32 {
+    if entry.effect == Powerup::Feast {
+        entry.magnitude as u32
+    } else {
+        0
+    }
+}
+
+// Checks whether a new food replaces the eaten one, `foods_left` doesn't count it.
+// A feast brings its own foods.
+pub fn replaces_food(entry: &PowerupEntry, foods_left: usize) -> bool {
+    entry.effect != Powerup::Feast && foods_left == 0
+}
+
+// Every tail part takes the position the part in front of it had.
+// `parts[0]` is the head and is left untouched.
+pub fn follow(parts: &mut [Cell]) {
+    for i in (1..parts.len()).rev() {
+        parts[i] = parts[i - 1];
+    }
+}
+
+// Checks whether the head overlaps any tail part.
+pub fn hits_tail<'a>(head: Cell, tails: impl ExactSizeIterator<Item = &'a Cell>) -> bool {
+    // A freshly grown single tail sits on the head for one tick,
+    // so a snake with one tail can't collide with itself.
+    if tails.len() <= 1 {
+        return false;
+    }
+
+    let mut tails = tails;
+    tails.any(|tail| *tail == head)
+}
+
+// Returns every field cell that is not in `taken`.
+pub fn free_cells<'a>(field: &[Cell], taken: impl Iterator<Item = &'a Cell>) -> Vec<Cell> {
+    let mut free: Vec<Cell> = field.to_vec();
+
+    for cell in taken {
+        if let Some(id) = free.iter().position(|c| c == cell) {
+            free.remove(id);
+        }
+    }
+
+    free
+}
+
+// Takes a random cell for food out of `free`, preferring the level's food spawns.
+pub fn take_food_cell(free: &mut Vec<Cell>, spawns: &[Cell], rng: &mut impl Rng) -> Option<Cell> {
+    let free_spawns: Vec<usize> = free
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| spawns.contains(cell))
+        .map(|(id, _)| id)
+        .collect();
+
+    if !free_spawns.is_empty() {
+        let id = free_spawns[rng.gen_range(0..free_spawns.len())];
+        Some(free.remove(id))
+    } else if !free.is_empty() {
+        let id = rng.gen_range(0..free.len());
+        Some(free.remove(id))
+    } else {
+        None
+    }
+}
+
+// Places up to `amount` new foods and picks the registry entry of each, `powerup` or a
+// random one. Snakes and food already on the board have to be in `taken`, walls are never
+// part of the field. The game and `SnakeSim` both place food through this, so a seed plays
+// out the same in both. Returns fewer foods when the board is full.
+pub fn place_foods<'a>(
+    field: &[Cell],
+    taken: impl Iterator<Item = &'a Cell>,
+    spawns: &[Cell],
+    registry: &PowerupRegistry,
+    powerup: Option<Powerup>,
+    amount: u32,
+    rng: &mut impl Rng,
+) -> Vec<(Cell, PowerupEntry)> {
+    let mut free = free_cells(field, taken);
+    let mut foods = Vec::new();
+
+    for _ in 0..amount {
+        let Some(cell) = take_food_cell(&mut free, spawns, rng) else {
+            break;
+        };
+
+        let entry = match powerup {
+            Some(powerup) => registry.get(powerup),
+            None => registry.get_random_entry(rng),
+        };
+
+        foods.push((cell, entry));
+    }
+
+    foods
+}
@@ -0,0 +1,13 @@
+use crate::libs::{cell::Cell, death::DeathCause, eatables::powerups::powerup::Powerup};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SimEvent {
+    Moved { head: Cell },
+    Ate { pos: Cell, powerup: Powerup },
+    FoodSpawned { pos: Cell, powerup: Powerup },
+    Grew,
+    Shortened(u32),
+    EffectStarted(Powerup),
+    EffectEnded(Powerup),
+    Died(DeathCause),
+}
@@ -0,0 +1,230 @@
+use crate::libs::{
+    cell::Cell,
+    death::DeathCause,
+    eatables::powerups::{powerup::Powerup, registry::PowerupRegistry},
+    game_configuration::{BoundaryMode, GameConfiguration},
+    input::direction::Direction,
+};
+
+use super::{sim_event::SimEvent, snake_sim::SnakeSim};
+
+// Grid channels before the one-per-powerup food channels.
+pub const HEAD_CHANNEL: usize = 0;
+pub const BODY_CHANNEL: usize = 1;
+pub const WALL_CHANNEL: usize = 2;
+pub const FOOD_CHANNELS: usize = 3;
+pub const CHANNELS: usize = FOOD_CHANNELS + Powerup::ALL.len();
+
+// Every action an agent can take, `None` keeps the current direction.
+pub const ACTIONS: [Option<Direction>; 5] = [
+    None,
+    Some(Direction::Up),
+    Some(Direction::Down),
+    Some(Direction::Left),
+    Some(Direction::Right),
+];
+
+// Channel holding food of `powerup`.
+pub fn food_channel(powerup: Powerup) -> usize {
+    let id = Powerup::ALL
+        .iter()
+        .position(|other| *other == powerup)
+        .unwrap_or(0);
+
+    FOOD_CHANNELS + id
+}
+
+// Board as a `CHANNELS x height x width` tensor, 1.0 where the channel's thing is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    pub width: u32,
+    pub height: u32,
+    pub grid: Vec<f32>,
+    // Direction the snake moved in on the last step.
+    pub direction: Direction,
+}
+
+impl Observation {
+    fn new(sim: &SnakeSim) -> Self {
+        let mut observation = Self {
+            width: sim.width,
+            height: sim.height,
+            grid: vec![0.; CHANNELS * (sim.width * sim.height) as usize],
+            direction: sim.direction,
+        };
+
+        observation.set(HEAD_CHANNEL, sim.head());
+
+        for part in sim.body.iter().skip(1) {
+            observation.set(BODY_CHANNEL, *part);
+        }
+
+        for wall in sim.walls.iter() {
+            observation.set(WALL_CHANNEL, *wall);
+        }
+
+        for food in sim.foods.iter() {
+            observation.set(food_channel(sim.registry.entry(&food.id).effect), food.pos);
+        }
+
+        observation
+    }
+
+    fn index(&self, channel: usize, cell: Cell) -> usize {
+        (channel as u32 * self.width * self.height + cell.y * self.width + cell.x) as usize
+    }
+
+    fn set(&mut self, channel: usize, cell: Cell) {
+        let id = self.index(channel, cell);
+        self.grid[id] = 1.;
+    }
+
+    pub fn get(&self, channel: usize, cell: Cell) -> f32 {
+        self.grid[self.index(channel, cell)]
+    }
+}
+
+// Reward for each thing that can happen on a step, added together.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RewardConfig {
+    // Per point scored, so score multipliers count too.
+    pub food: f32,
+    pub death: f32,
+    // Every step, negative values push the agent to hurry.
+    pub step: f32,
+    // Per cell the head got closer to the nearest food, negative when moving away.
+    pub approach: f32,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        Self {
+            food: 1.,
+            death: -1.,
+            step: 0.,
+            approach: 0.,
+        }
+    }
+}
+
+// What happened on a step besides the reward.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepInfo {
+    pub events: Vec<SimEvent>,
+    pub score: u32,
+    pub tick: u64,
+    pub death: Option<DeathCause>,
+    // The episode hit `SnakeEnv::max_ticks` without dying.
+    pub truncated: bool,
+}
+
+// Gym-style environment over `SnakeSim`, so agents play by the same rules as the game.
+// No rendering or timers are involved, a step is as fast as the rules allow.
+// An episode is a single-player game with one snake, there are no versus or bot opponents.
+pub struct SnakeEnv {
+    pub configuration: GameConfiguration,
+    pub registry: PowerupRegistry,
+    pub rewards: RewardConfig,
+    // Episodes end after this many steps when set.
+    pub max_ticks: Option<u64>,
+    sim: SnakeSim,
+}
+
+impl SnakeEnv {
+    pub fn new(
+        configuration: GameConfiguration,
+        registry: PowerupRegistry,
+        rewards: RewardConfig,
+    ) -> Self {
+        let sim = SnakeSim::new(&configuration, &registry, 0);
+
+        Self {
+            configuration,
+            registry,
+            rewards,
+            max_ticks: None,
+            sim,
+        }
+    }
+
+    // Starts a new episode, the same seed and actions always play out the same.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.sim = SnakeSim::new(&self.configuration, &self.registry, seed);
+        self.observation()
+    }
+
+    pub fn observation(&self) -> Observation {
+        Observation::new(&self.sim)
+    }
+
+    pub fn sim(&self) -> &SnakeSim {
+        &self.sim
+    }
+
+    // Plays one tick. Stepping a finished episode does nothing and returns `done` again.
+    pub fn step(&mut self, action: Option<Direction>) -> (Observation, f32, bool, StepInfo) {
+        let score = self.sim.score;
+        let distance = self.food_distance();
+
+        let events = self.sim.step(action);
+
+        let death = events.iter().find_map(|event| match event {
+            SimEvent::Died(cause) => Some(*cause),
+            _ => None,
+        });
+
+        let mut reward = self.rewards.step + self.rewards.food * (self.sim.score - score) as f32;
+
+        if death.is_some() {
+            reward += self.rewards.death;
+        } else if let (Some(before), Some(after)) = (distance, self.food_distance()) {
+            reward += self.rewards.approach * (before as f32 - after as f32);
+        }
+
+        let truncated = !self.sim.game_over
+            && self
+                .max_ticks
+                .is_some_and(|max_ticks| self.sim.tick >= max_ticks);
+
+        let info = StepInfo {
+            events,
+            score: self.sim.score,
+            tick: self.sim.tick,
+            death,
+            truncated,
+        };
+
+        (
+            self.observation(),
+            reward,
+            self.sim.game_over || truncated,
+            info,
+        )
+    }
+
+    // Steps from the head to the nearest food, ignoring walls. Counts steps across
+    // the edges when the board wraps.
+    fn food_distance(&self) -> Option<u32> {
+        let head = self.sim.head();
+        let wraps = self.sim.boundary_mode == BoundaryMode::Wrap;
+
+        let distance = |from: u32, to: u32, size: u32| {
+            let straight = from.abs_diff(to);
+
+            if wraps {
+                straight.min(size - straight)
+            } else {
+                straight
+            }
+        };
+
+        self.sim
+            .foods
+            .iter()
+            .map(|food| {
+                distance(head.x, food.pos.x, self.sim.width)
+                    + distance(head.y, food.pos.y, self.sim.height)
+            })
+            .min()
+    }
+}
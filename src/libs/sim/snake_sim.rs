@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+
+use crate::libs::{
+    cell::Cell,
+    death::DeathCause,
+    eatables::powerups::{
+        active_effects::ActiveEffects, powerup::Powerup, registry::PowerupRegistry,
+    },
+    game_configuration::{BoundaryMode, GameConfiguration, GameDifficulty},
+    game_rng::GameRng,
+    input::direction::Direction,
+};
+
+use super::{rules, sim_event::SimEvent};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimFood {
+    pub pos: Cell,
+    // Id of the registry entry, like `Food`.
+    pub id: String,
+}
+
+// Headless single-player snake game. One `step` is one tick of `GameConfiguration::tick_timer`.
+#[derive(Clone, Debug)]
+pub struct SnakeSim {
+    pub width: u32,
+    pub height: u32,
+    pub difficulty: GameDifficulty,
+    pub boundary_mode: BoundaryMode,
+    // `body[0]` is the head.
+    pub body: Vec<Cell>,
+    pub direction: Direction,
+    pub planned_direction: VecDeque<Direction>,
+    pub walls: Vec<Cell>,
+    pub foods: Vec<SimFood>,
+    pub score: u32,
+    pub tick: u64,
+    pub effects: ActiveEffects,
+    pub game_over: bool,
+    pub registry: PowerupRegistry,
+    field: Vec<Cell>,
+    food_spawns: Vec<Cell>,
+    rng: GameRng,
+}
+
+impl SnakeSim {
+    // Starts a game with the difficulty and board of `configuration`.
+    // Foods and their effects come from `registry`.
+    // The same `seed` and the same inputs always produce the same game.
+    pub fn new(configuration: &GameConfiguration, registry: &PowerupRegistry, seed: u64) -> Self {
+        let (start, direction) = configuration.start_position();
+
+        let mut sim = Self {
+            width: configuration.grid_width,
+            height: configuration.grid_height,
+            difficulty: configuration.current_difficulty,
+            boundary_mode: configuration.boundary_mode,
+            body: vec![start],
+            direction,
+            planned_direction: VecDeque::new(),
+            walls: configuration.walls().to_vec(),
+            foods: Vec::new(),
+            score: 0,
+            tick: 0,
+            effects: ActiveEffects::default(),
+            game_over: false,
+            registry: registry.clone(),
+            field: configuration.field.clone(),
+            food_spawns: configuration.food_spawns().to_vec(),
+            rng: GameRng::new(seed),
+        };
+
+        sim.spawn_foods(Some(Powerup::Normal), 1, &mut Vec::new());
+        sim
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn head(&self) -> Cell {
+        self.body[0]
+    }
+
+    // Seconds per tick with all active effects applied.
+    pub fn tick_rate(&self) -> f32 {
+        self.effects
+            .tick_rate(self.difficulty.get_tick_rate(), &self.registry)
+    }
+
+    // Advances the game by one tick. `input` is the direction pressed since the last step.
+    pub fn step(&mut self, input: Option<Direction>) -> Vec<SimEvent> {
+        let mut events: Vec<SimEvent> = Vec::new();
+
+        if self.game_over {
+            return events;
+        }
+
+        if let Some(direction) = input {
+            rules::plan_direction(&mut self.planned_direction, direction);
+        }
+
+        self.tick += 1;
+        for powerup in self.effects.tick() {
+            events.push(SimEvent::EffectEnded(powerup));
+        }
+
+        self.direction = rules::next_direction(self.direction, &mut self.planned_direction);
+
+        let Some(new_head) = rules::move_cell(
+            self.head(),
+            self.direction,
+            self.width,
+            self.height,
+            self.boundary_mode,
+        ) else {
+            self.game_over = true;
+            events.push(SimEvent::Died(DeathCause::Wall));
+            return events;
+        };
+
+        rules::follow(&mut self.body);
+        self.body[0] = new_head;
+        events.push(SimEvent::Moved { head: new_head });
+
+        if self.walls.contains(&new_head) {
+            self.game_over = true;
+            events.push(SimEvent::Died(DeathCause::Wall));
+            return events;
+        }
+
+        if !self.effects.is_active(Powerup::Ghost)
+            && rules::hits_tail(self.head(), self.body[1..].iter())
+        {
+            self.game_over = true;
+            events.push(SimEvent::Died(DeathCause::SelfCollision));
+            return events;
+        }
+
+        let head = self.head();
+        if let Some(id) = self.foods.iter().position(|food| food.pos == head) {
+            let food = self.foods.remove(id);
+            self.eat(food, &mut events);
+        }
+
+        events
+    }
+
+    fn eat(&mut self, food: SimFood, events: &mut Vec<SimEvent>) {
+        let entry = self.registry.entry(&food.id);
+
+        self.score += self.effects.score_multiplier(&self.registry);
+        events.push(SimEvent::Ate {
+            pos: food.pos,
+            powerup: entry.effect,
+        });
+
+        if entry.duration > 0 {
+            self.effects.start(&entry);
+            events.push(SimEvent::EffectStarted(entry.effect));
+        }
+
+        let amount = rules::tails_removed(&entry, self.body.len());
+        if amount > 0 {
+            self.body.truncate(self.body.len() - amount as usize);
+            events.push(SimEvent::Shortened(amount));
+        }
+
+        if rules::grows(&entry) {
+            // New tail stays in place of the last part and is pulled along next tick.
+            self.body.push(*self.body.last().unwrap());
+            events.push(SimEvent::Grew);
+        }
+
+        let amount = rules::feast_foods(&entry);
+        if amount > 0 {
+            self.spawn_foods(None, amount, events);
+        }
+
+        if rules::replaces_food(&entry, self.foods.len()) {
+            self.spawn_foods(None, 1, events);
+        }
+    }
+
+    fn spawn_foods(&mut self, powerup: Option<Powerup>, amount: u32, events: &mut Vec<SimEvent>) {
+        let taken = self
+            .body
+            .iter()
+            .chain(self.foods.iter().map(|food| &food.pos));
+        let foods = rules::place_foods(
+            &self.field,
+            taken,
+            &self.food_spawns,
+            &self.registry,
+            powerup,
+            amount,
+            &mut self.rng,
+        );
+
+        for (pos, entry) in foods {
+            self.foods.push(SimFood { pos, id: entry.id });
+            events.push(SimEvent::FoodSpawned {
+                pos,
+                powerup: entry.effect,
+            });
+        }
+    }
+}
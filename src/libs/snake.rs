@@ -2,13 +2,20 @@ use std::collections::VecDeque;
 
 use super::{
     audio::AudioAssets,
+    bots::Bot,
     cell::{Cell, CellBundle},
-    eatables::{eat_event::EatEvent, food::Food, powerups::powerup::Powerup},
+    death::{DeathCause, DeathEvent},
+    eatables::{
+        eat_event::EatEvent,
+        food::Food,
+        powerups::{active_effects::ActiveEffects, powerup::Powerup, registry::PowerupRegistry},
+    },
     game_configuration::GameConfiguration,
     game_states::GameState,
-    globals::{GRID_CENTER, GRID_SIZE, HEAD_COLOR, TAIL_COLOR},
     input::{action_events::ActionMoveEvent, direction::Direction},
+    player::PlayerId,
     schedule::InGameSet,
+    sim::rules,
 };
 use bevy::{
     audio::{PlaybackMode, Volume, VolumeLevel},
@@ -30,72 +37,85 @@ pub struct HeadBundle {
     cell: CellBundle,
 }
 
-#[derive(Resource, Default, Reflect)]
+// Every part of one player's snake, `parts[0]` is the head. Kept on the head entity.
+#[derive(Component, Default, Reflect)]
 pub struct Snake {
     pub parts: Vec<Entity>,
 }
 
 impl Snake {
-    pub fn create(commands: &mut Commands, snake: &mut ResMut<Snake>) {
-        let cell = Cell {
-            x: GRID_CENTER,
-            y: GRID_CENTER,
-        };
+    pub fn create(
+        commands: &mut Commands,
+        player: PlayerId,
+        start: (Cell, Direction),
+        configuration: &GameConfiguration,
+    ) -> Entity {
+        let (cell, direction) = start;
 
-        let id = commands
-            .spawn(CellBundle::new_with_z(cell, HEAD_COLOR, 1.))
-            .insert(Head {
+        let mut head = commands.spawn((
+            CellBundle::new_with_z(cell, player.head_color(configuration), 1., configuration),
+            Head {
                 planned_direction: VecDeque::new(),
-                direction: Direction::Up,
-            })
-            .id();
-        snake.parts.push(id);
+                direction,
+            },
+            player,
+        ));
+
+        let id = head.id();
+        head.insert((Snake { parts: vec![id] }, ActiveEffects::default()));
+
+        id
     }
 
     pub fn new_tail(
+        &mut self,
         commands: &mut Commands,
         query: &Query<&Cell, Without<Food>>,
-        snake: &mut ResMut<Snake>,
+        player: PlayerId,
+        configuration: &GameConfiguration,
     ) {
-        let last_id = snake.parts.last().unwrap();
+        let last_id = self.parts.last().unwrap();
         let last_cell_search = query.get(*last_id);
 
         if let Ok(last_cell) = last_cell_search {
             let id = commands
-                .spawn(CellBundle::new(*last_cell, TAIL_COLOR))
-                .insert(Tail)
+                .spawn(CellBundle::new(
+                    *last_cell,
+                    player.tail_color(configuration),
+                    configuration,
+                ))
+                .insert((Tail, player))
                 .id();
-            snake.parts.push(id);
+            self.parts.push(id);
         }
     }
 
     pub fn remove_tails(
+        &mut self,
         commands: &mut Commands,
         query: &Query<Entity, With<Tail>>,
-        snake: &mut ResMut<Snake>,
         amount: u32,
     ) {
         for _ in 0..amount {
-            let last_id = snake.parts.last().unwrap();
+            let last_id = self.parts.last().unwrap();
             let last_tail = query.get(*last_id);
 
             if let Ok(id) = last_tail {
-                commands.entity(id).despawn();
-                snake.parts.pop();
+                commands.entity(id).despawn_recursive();
+                self.parts.pop();
             }
         }
     }
 }
 
-fn set_snake_direction(mut ev_move: EventReader<ActionMoveEvent>, mut query: Query<&mut Head>) {
+fn set_snake_direction(
+    mut ev_move: EventReader<ActionMoveEvent>,
+    mut query: Query<(&mut Head, &PlayerId)>,
+) {
     for evt in ev_move.read() {
-        if let Ok(mut head) = query.get_single_mut() {
-            if let Some(last_dir) = head.planned_direction.back() {
-                if *last_dir != evt.0 {
-                    head.planned_direction.push_back(evt.0);
-                }
-            } else {
-                head.planned_direction.push_back(evt.0);
+        for (mut head, player) in query.iter_mut() {
+            if *player == evt.player {
+                rules::plan_direction(&mut head.planned_direction, evt.direction);
             }
         }
     }
@@ -103,83 +123,67 @@ fn set_snake_direction(mut ev_move: EventReader<ActionMoveEvent>, mut query: Que
 
 fn move_head(
     mut commands: Commands,
-    mut query: Query<(&mut Cell, &mut Head)>,
-    audio: Res<AudioAssets>,
+    mut query: Query<(&mut Cell, &mut Head, &PlayerId)>,
+    audio: Option<Res<AudioAssets>>,
     game_configration: Res<GameConfiguration>,
+    mut ev_death: EventWriter<DeathEvent>,
 ) {
     if game_configration.tick_timer.just_finished() {
-        if let Ok((mut cell, mut head)) = query.get_single_mut() {
-            let planned_direction = head.planned_direction.pop_front();
-
-            if let Some(plan_dir) = planned_direction {
-                if head.direction != plan_dir.opposite() {
-                    head.direction = plan_dir;
-                } else {
-                    head.planned_direction.clear();
-                }
-            }
+        for (mut cell, mut head, player) in query.iter_mut() {
+            let head = &mut *head;
+            head.direction = rules::next_direction(head.direction, &mut head.planned_direction);
+            let new_cell = rules::move_cell(
+                *cell,
+                head.direction,
+                game_configration.grid_width,
+                game_configration.grid_height,
+                game_configration.boundary_mode,
+            );
 
-            match head.direction {
-                Direction::Up => {
-                    if cell.y == 0 {
-                        cell.y = GRID_SIZE - 1;
-                    } else {
-                        cell.y -= 1;
-                    }
-                }
-                Direction::Down => {
-                    if cell.y == GRID_SIZE - 1 {
-                        cell.y = 0;
-                    } else {
-                        cell.y += 1;
-                    }
-                }
-                Direction::Left => {
-                    if cell.x == 0 {
-                        cell.x = GRID_SIZE - 1;
-                    } else {
-                        cell.x -= 1;
-                    }
-                }
-                Direction::Right => {
-                    if cell.x == GRID_SIZE - 1 {
-                        cell.x = 0;
-                    } else {
-                        cell.x += 1;
-                    }
-                }
+            match new_cell {
+                Some(new_cell) => *cell = new_cell,
+                None => ev_death.send(DeathEvent {
+                    player: *player,
+                    cause: DeathCause::Wall,
+                }),
             }
         }
 
-        commands.spawn(AudioBundle {
-            source: audio.snake_movement_sound.clone(),
-            settings: PlaybackSettings {
-                volume: Volume::Relative(VolumeLevel::new(0.3)),
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
-        });
+        // No sound when the game runs without the audio plugin, e.g. in tests.
+        if let Some(audio) = audio {
+            commands.spawn(AudioBundle {
+                source: audio.snake_movement_sound.clone(),
+                settings: PlaybackSettings {
+                    volume: Volume::Relative(VolumeLevel::new(0.3)),
+                    mode: PlaybackMode::Despawn,
+                    ..default()
+                },
+            });
+        }
     }
 }
 
 fn move_tail(
-    mut query: Query<(Entity, &mut Cell), Without<Food>>,
-    snake: Res<Snake>,
+    mut query: Query<&mut Cell, Without<Food>>,
+    snakes: Query<&Snake>,
     game_configration: Res<GameConfiguration>,
 ) {
     if game_configration.tick_timer.just_finished() {
-        let mut current_snake_parts: Vec<(Entity, Cell)> = vec![];
+        for snake in snakes.iter() {
+            let mut current_snake_parts: Vec<Cell> = vec![];
 
-        for part in snake.parts.iter() {
-            if let Ok(e) = query.get(*part) {
-                current_snake_parts.push((e.0, *e.1));
+            for part in snake.parts.iter() {
+                if let Ok(cell) = query.get(*part) {
+                    current_snake_parts.push(*cell);
+                }
             }
-        }
 
-        for (i, tail_id) in snake.parts.iter().enumerate().skip(1) {
-            if let Ok(mut world_tail) = query.get_mut(*tail_id) {
-                let previous_part = current_snake_parts.get(i - 1).unwrap();
-                *world_tail.1 = previous_part.1;
+            rules::follow(&mut current_snake_parts);
+
+            for (tail_id, new_cell) in snake.parts.iter().zip(current_snake_parts).skip(1) {
+                if let Ok(mut cell) = query.get_mut(*tail_id) {
+                    *cell = new_cell;
+                }
             }
         }
     }
@@ -189,52 +193,110 @@ fn grow_snake_on_eat(
     mut ev_eat: EventReader<EatEvent>,
     mut commands: Commands,
     query: Query<&Cell, Without<Food>>,
-    mut snake: ResMut<Snake>,
-    audio: Res<AudioAssets>,
+    mut snakes: Query<(&mut Snake, &PlayerId)>,
+    audio: Option<Res<AudioAssets>>,
+    configuration: Res<GameConfiguration>,
+    registry: Res<PowerupRegistry>,
 ) {
     for ev in ev_eat.read() {
-        if ev.food.0 == Powerup::Shorten {
-            return;
+        if !rules::grows(&registry.entry(&ev.food.0)) {
+            continue;
         }
 
-        Snake::new_tail(&mut commands, &query, &mut snake);
+        for (mut snake, player) in snakes.iter_mut() {
+            if *player == ev.player {
+                snake.new_tail(&mut commands, &query, *player, &configuration);
+            }
+        }
 
-        commands.spawn(AudioBundle {
-            source: audio.eat_sound.clone(),
-            settings: PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
-        });
+        if let Some(audio) = &audio {
+            commands.spawn(AudioBundle {
+                source: audio.eat_sound.clone(),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Despawn,
+                    ..default()
+                },
+            });
+        }
     }
 }
 
-fn spawn_snake(mut commands: Commands, mut snake: ResMut<Snake>) {
-    Snake::create(&mut commands, &mut snake);
-}
+fn spawn_snake(mut commands: Commands, configuration: Res<GameConfiguration>) {
+    let players = PlayerId::all(configuration.snake_count());
+
+    for (player, start) in players.zip(configuration.start_positions()) {
+        let head = Snake::create(&mut commands, player, start, &configuration);
 
-fn destroy_snake(mut commands: Commands, mut snake: ResMut<Snake>) {
-    for part in snake.parts.iter() {
-        commands.entity(*part).despawn();
+        if configuration.is_bot(player) {
+            commands.entity(head).insert(Bot);
+        }
     }
+}
 
-    snake.parts.clear();
+fn destroy_snake(mut commands: Commands, query: Query<&Snake>) {
+    for snake in query.iter() {
+        for part in snake.parts.iter() {
+            commands.entity(*part).despawn_recursive();
+        }
+    }
 }
 
 fn snake_self_collision(
-    head: Query<&Cell, With<Head>>,
+    heads: Query<(&Cell, &Snake, &PlayerId, &ActiveEffects)>,
     tails: Query<&Cell, With<Tail>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut ev_death: EventWriter<DeathEvent>,
 ) {
-    // Len check is a dirty hack, but it works.
-    if tails.iter().len() > 1 {
-        if let Ok(head) = head.get_single() {
-            for tails in tails.iter() {
-                if *head == *tails {
-                    next_state.set(GameState::FinishMenu);
-                }
-            }
+    for (head, snake, player, effects) in heads.iter() {
+        // Ghost snake passes through its own tail.
+        if effects.is_active(Powerup::Ghost) {
+            continue;
         }
+
+        let own_tails: Vec<Cell> = snake
+            .parts
+            .iter()
+            .filter_map(|part| tails.get(*part).ok())
+            .copied()
+            .collect();
+
+        if rules::hits_tail(*head, own_tails.iter()) {
+            ev_death.send(DeathEvent {
+                player: *player,
+                cause: DeathCause::SelfCollision,
+            });
+        }
+    }
+}
+
+// Heads meeting kill both snakes, a head in another snake's body kills only that head's snake.
+fn snakes_collision(
+    heads: Query<(&Cell, &PlayerId, &ActiveEffects), With<Head>>,
+    tails: Query<(&Cell, &PlayerId), With<Tail>>,
+    mut ev_death: EventWriter<DeathEvent>,
+) {
+    for (head, player, effects) in heads.iter() {
+        let head_on = heads
+            .iter()
+            .any(|(other_head, other, _)| other != player && other_head == head);
+
+        // Ghosts pass through bodies, not through each other's heads.
+        let hits_body = !effects.is_active(Powerup::Ghost)
+            && tails
+                .iter()
+                .any(|(tail, owner)| owner != player && tail == head);
+
+        let cause = if head_on {
+            DeathCause::HeadOn
+        } else if hits_body {
+            DeathCause::SnakeCollision
+        } else {
+            continue;
+        };
+
+        ev_death.send(DeathEvent {
+            player: *player,
+            cause,
+        });
     }
 }
 
@@ -245,7 +307,7 @@ impl Plugin for SnakePlugin {
         app.register_type::<Head>()
             .register_type::<Tail>()
             .register_type::<Snake>()
-            .insert_resource(Snake::default())
+            .register_type::<PlayerId>()
             .add_systems(OnEnter(GameState::InGame), spawn_snake)
             .add_systems(
                 Update,
@@ -260,7 +322,7 @@ impl Plugin for SnakePlugin {
             )
             .add_systems(
                 Update,
-                snake_self_collision.in_set(InGameSet::CollisionDetection),
+                (snake_self_collision, snakes_collision).in_set(InGameSet::CollisionDetection),
             );
     }
 }
@@ -0,0 +1,320 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use bevy::prelude::*;
+
+use super::{
+    cell::Cell,
+    game_configuration::GameConfiguration,
+    globals::BACKGROUND_COLOR,
+    input::direction::Direction,
+    schedule::InGameSet,
+    sim::rules,
+    snake::{Head, Snake, Tail},
+};
+
+pub const SKIN_FILE: &str = "skins/classic.png";
+// Pixel size of one piece in the skin, the pieces sit in one row in `BodyPieceKind` order.
+const SKIN_TILE_SIZE: f32 = 32.;
+
+// Sizes relative to a cell, the body is narrower than a cell so turns and parallel parts show.
+const BODY_WIDTH: f32 = 0.7;
+const TAIL_END_WIDTH: f32 = 0.45;
+const EYE_SIZE: f32 = 0.14;
+const EYE_FORWARD: f32 = 0.12;
+const EYE_APART: f32 = 0.17;
+
+// Every piece is drawn pointing up, `BodyPiece::rotation` turns it.
+// The head points away from the body, the tail end and both ends of a straight piece
+// point at a neighbour and a corner joins the part above with the part to the right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BodyPieceKind {
+    Head,
+    Straight,
+    Corner,
+    TailEnd,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BodyPiece {
+    pub kind: BodyPieceKind,
+    pub rotation: Direction,
+}
+
+// Piece of a tail part from the directions to the parts in front of and behind it.
+pub fn body_piece(front: Option<Direction>, back: Option<Direction>) -> Option<BodyPiece> {
+    let (kind, rotation) = match (front, back) {
+        (Some(front), Some(back)) if front == back.opposite() => (BodyPieceKind::Straight, front),
+        (Some(front), Some(back)) => {
+            let rotation = Direction::ALL.into_iter().find(|rotation| {
+                (*rotation == front && rotation.clockwise() == back)
+                    || (*rotation == back && rotation.clockwise() == front)
+            })?;
+
+            (BodyPieceKind::Corner, rotation)
+        }
+        (Some(front), None) => (BodyPieceKind::TailEnd, front),
+        _ => return None,
+    };
+
+    Some(BodyPiece { kind, rotation })
+}
+
+// Atlas with the textured pieces, used instead of plain shapes when `snake_skin` is on.
+#[derive(Resource, Default)]
+pub struct SnakeSkin {
+    pub atlas: Handle<TextureAtlas>,
+}
+
+// Strip of body reaching from the part towards its front (0) or back (1) neighbour.
+#[derive(Component)]
+struct Joint(usize);
+
+// -1 for the left eye, 1 for the right one, looking the way the head moves.
+#[derive(Component)]
+struct Eye(f32);
+
+#[derive(Component)]
+struct SkinPiece;
+
+fn unit(direction: Direction) -> Vec2 {
+    match direction {
+        Direction::Up => Vec2::Y,
+        Direction::Down => Vec2::NEG_Y,
+        Direction::Left => Vec2::NEG_X,
+        Direction::Right => Vec2::X,
+    }
+}
+
+fn rotation(direction: Direction) -> Quat {
+    let angle = match direction {
+        Direction::Up => 0.,
+        Direction::Left => FRAC_PI_2,
+        Direction::Down => PI,
+        Direction::Right => -FRAC_PI_2,
+    };
+
+    Quat::from_rotation_z(angle)
+}
+
+// Size and offset of the strip joining a core of `core` width to the neighbour in `direction`.
+fn joint_shape(direction: Direction, core: f32, cell_size: f32) -> Option<(Vec2, Vec3)> {
+    if core == 0. {
+        return None;
+    }
+
+    // From the edge of the core to the edge of the cell.
+    let length = (cell_size - core) / 2.;
+    let size = match direction {
+        Direction::Up | Direction::Down => Vec2::new(core, length),
+        Direction::Left | Direction::Right => Vec2::new(length, core),
+    };
+
+    Some((size, (unit(direction) * (core + length) / 2.).extend(0.)))
+}
+
+fn eye_shape(direction: Direction, side: f32, core: f32, cell_size: f32) -> Option<(Vec2, Vec3)> {
+    if core == 0. {
+        return None;
+    }
+
+    let offset = unit(direction) * EYE_FORWARD + unit(direction.clockwise()) * side * EYE_APART;
+
+    Some((
+        Vec2::splat(EYE_SIZE * cell_size),
+        (offset * cell_size).extend(0.1),
+    ))
+}
+
+fn load_skin(
+    mut skin: ResMut<SnakeSkin>,
+    asset_server: Res<AssetServer>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let texture = asset_server.load(SKIN_FILE);
+    let atlas = TextureAtlas::from_grid(texture, Vec2::splat(SKIN_TILE_SIZE), 4, 1, None, None);
+
+    skin.atlas = atlases.add(atlas);
+}
+
+// Every part gets its joints, eyes and skin piece once, they are shown or hidden as needed.
+fn attach_body_pieces(
+    mut commands: Commands,
+    parts: Query<(Entity, Has<Head>), Or<(Added<Head>, Added<Tail>)>>,
+    skin: Res<SnakeSkin>,
+) {
+    for (part, is_head) in parts.iter() {
+        commands.entity(part).with_children(|parent| {
+            for side in 0..2 {
+                parent.spawn((
+                    SpriteBundle {
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    Joint(side),
+                ));
+            }
+
+            if is_head {
+                for side in [-1., 1.] {
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: BACKGROUND_COLOR,
+                                ..default()
+                            },
+                            visibility: Visibility::Hidden,
+                            ..default()
+                        },
+                        Eye(side),
+                    ));
+                }
+            }
+
+            parent.spawn((
+                SpriteSheetBundle {
+                    texture_atlas: skin.atlas.clone(),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                SkinPiece,
+            ));
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn shape_snake_bodies(
+    snakes: Query<(&Snake, &Head)>,
+    mut parts: Query<(&Cell, &mut Sprite, Option<&Children>)>,
+    mut shapes: Query<
+        (
+            &mut Sprite,
+            &mut Transform,
+            &mut Visibility,
+            Option<&Joint>,
+            Option<&Eye>,
+        ),
+        (Without<Cell>, Without<SkinPiece>),
+    >,
+    mut skin_pieces: Query<
+        (&mut TextureAtlasSprite, &mut Transform, &mut Visibility),
+        (With<SkinPiece>, Without<Cell>),
+    >,
+    configuration: Res<GameConfiguration>,
+) {
+    let cell_size = configuration.cell_size;
+    let (width, height) = (configuration.grid_width, configuration.grid_height);
+
+    for (snake, head) in snakes.iter() {
+        let cells: Vec<Option<Cell>> = snake
+            .parts
+            .iter()
+            .map(|part| parts.get(*part).ok().map(|(cell, _, _)| *cell))
+            .collect();
+
+        // Neighbours across a wrapping edge count too, so the joints reach the board edge.
+        let towards = |from: Option<Cell>, to: Option<&Option<Cell>>| match (from, to) {
+            (Some(from), Some(Some(to))) => rules::neighbour_direction(from, *to, width, height),
+            _ => None,
+        };
+
+        for (i, part) in snake.parts.iter().enumerate() {
+            let Ok((_, mut sprite, children)) = parts.get_mut(*part) else {
+                continue;
+            };
+
+            let front = i
+                .checked_sub(1)
+                .and_then(|front| towards(cells[i], cells.get(front)));
+            let back = towards(cells[i], cells.get(i + 1));
+
+            // A freshly grown part sits on the one in front of it until the next move.
+            let stacked = i > 0 && cells[i] == cells[i - 1];
+
+            let piece = if i == 0 {
+                Some(BodyPiece {
+                    kind: BodyPieceKind::Head,
+                    rotation: head.direction,
+                })
+            } else {
+                body_piece(front, back)
+            };
+
+            let skinned = configuration.snake_skin && piece.is_some() && !stacked;
+
+            let core = if stacked || skinned {
+                0.
+            } else if piece.is_some_and(|piece| piece.kind == BodyPieceKind::TailEnd) {
+                TAIL_END_WIDTH * cell_size
+            } else {
+                BODY_WIDTH * cell_size
+            };
+
+            sprite.custom_size = Some(Vec2::splat(core));
+            let color = sprite.color;
+
+            let Some(children) = children else {
+                continue;
+            };
+
+            for child in children.iter() {
+                if let Ok((mut sprite, mut transform, mut visibility, joint, eye)) =
+                    shapes.get_mut(*child)
+                {
+                    sprite.color = match eye {
+                        Some(_) => BACKGROUND_COLOR.with_a(color.a()),
+                        None => color,
+                    };
+
+                    let shape = match (joint, eye) {
+                        (Some(joint), _) => {
+                            let direction = if joint.0 == 0 { front } else { back };
+                            direction.and_then(|direction| joint_shape(direction, core, cell_size))
+                        }
+                        (_, Some(eye)) => eye_shape(head.direction, eye.0, core, cell_size),
+                        _ => None,
+                    };
+
+                    *visibility = match shape {
+                        Some((size, offset)) => {
+                            sprite.custom_size = Some(size);
+                            transform.translation = offset;
+                            Visibility::Inherited
+                        }
+                        None => Visibility::Hidden,
+                    };
+                }
+
+                if let Ok((mut atlas_sprite, mut transform, mut visibility)) =
+                    skin_pieces.get_mut(*child)
+                {
+                    *visibility = match piece.filter(|_| skinned) {
+                        Some(piece) => {
+                            atlas_sprite.index = piece.kind as usize;
+                            atlas_sprite.color = color;
+                            atlas_sprite.custom_size = Some(Vec2::splat(cell_size));
+                            transform.rotation = rotation(piece.rotation);
+                            Visibility::Inherited
+                        }
+                        None => Visibility::Hidden,
+                    };
+                }
+            }
+        }
+    }
+}
+
+pub struct SnakeBodyPlugin;
+
+impl Plugin for SnakeBodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SnakeSkin::default())
+            .add_systems(Startup, load_skin)
+            .add_systems(
+                Update,
+                (attach_body_pieces, shape_snake_bodies)
+                    .chain()
+                    .in_set(InGameSet::GlobalPostionUpdates),
+            );
+    }
+}
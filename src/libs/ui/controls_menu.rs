@@ -0,0 +1,388 @@
+use bevy::prelude::*;
+
+use crate::libs::{
+    game_states::GameState,
+    input::bindings::{
+        ActionInput, BindingCapture, BindingTarget, InputAction, InputBindings, SteeringMode,
+    },
+    player::{PlayerId, MAX_PLAYERS},
+};
+
+use super::ui_utils::{despawn_ui, Focusable, BUTTON_COLOR};
+
+// The screen is split in two, the versus keys don't fit next to the others.
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+enum ControlsPage {
+    #[default]
+    Actions,
+    Versus,
+}
+
+enum ControlsButton {
+    Rebind(BindingTarget),
+    Clear(BindingTarget),
+    Steering(PlayerId),
+    Page(ControlsPage),
+    Reset,
+    Back,
+}
+
+#[derive(Component)]
+struct ControlsButtonTag(ControlsButton);
+
+#[derive(Component)]
+struct BindingTextTag(BindingTarget);
+
+#[derive(Component)]
+struct SteeringTextTag(PlayerId);
+
+fn text_style(font_size: f32) -> TextStyle {
+    TextStyle {
+        font_size,
+        color: Color::rgb(0.9, 0.9, 0.9),
+        ..default()
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, button: ControlsButton, text: &str, size: Vec2) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(size.x),
+                    height: Val::Px(size.y),
+                    border: UiRect::all(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(3.0)),
+                    ..default()
+                },
+                border_color: BorderColor(Color::BLACK),
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+            ControlsButtonTag(button),
+            Focusable,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(text, text_style(24.0)));
+        });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, spawn_children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(spawn_children);
+}
+
+fn target_name(target: BindingTarget) -> String {
+    match target {
+        BindingTarget::Action(action) => format!("{action:?}"),
+        BindingTarget::Versus(player, action) => format!("P{} {action:?}", player.number()),
+    }
+}
+
+fn binding_text(
+    target: BindingTarget,
+    bindings: &InputBindings,
+    capture: &BindingCapture,
+) -> String {
+    if capture.target == Some(target) {
+        return match capture.conflict {
+            Some(other) => format!("Used by {}, press another", target_name(other)),
+            None => "Press a key or button".to_string(),
+        };
+    }
+
+    let keys: Vec<String> = bindings
+        .keys(target)
+        .iter()
+        .map(|key| format!("{key:?}"))
+        .collect();
+
+    let BindingTarget::Action(action) = target else {
+        return keys.join(", ");
+    };
+
+    let buttons: Vec<String> = bindings
+        .get(action)
+        .into_iter()
+        .flat_map(|binding| binding.buttons.iter())
+        .map(|button| format!("{button:?}"))
+        .collect();
+
+    format!("{} / {}", keys.join(", "), buttons.join(", "))
+}
+
+fn steering_text(player: PlayerId, bindings: &InputBindings) -> String {
+    let mode = match bindings.steering[player.0] {
+        SteeringMode::DPad => "D-pad",
+        SteeringMode::Stick => "Left stick",
+        SteeringMode::Both => "D-pad and left stick",
+    };
+
+    format!("Player {} steers with: {mode}", player.number())
+}
+
+fn reset_page(mut commands: Commands) {
+    commands.insert_resource(ControlsPage::default());
+}
+
+// Builds the shown page again whenever the page changes.
+fn create_menu(
+    mut commands: Commands,
+    page: Res<ControlsPage>,
+    bindings: Res<InputBindings>,
+    capture: Res<BindingCapture>,
+) {
+    let row_button = Vec2::new(100.0, 40.0);
+    let menu_button = Vec2::new(200.0, 50.0);
+
+    let targets: Vec<BindingTarget> = match *page {
+        ControlsPage::Actions => InputAction::ALL
+            .into_iter()
+            .map(BindingTarget::Action)
+            .collect(),
+        ControlsPage::Versus => PlayerId::all(MAX_PLAYERS)
+            .flat_map(|player| {
+                InputAction::STEERING
+                    .into_iter()
+                    .map(move |action| BindingTarget::Versus(player, action))
+            })
+            .collect(),
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for target in targets {
+                spawn_row(parent, |parent| {
+                    parent.spawn(
+                        TextBundle::from_section(target_name(target), text_style(26.0)).with_style(
+                            Style {
+                                width: Val::Px(110.0),
+                                ..default()
+                            },
+                        ),
+                    );
+
+                    parent.spawn((
+                        TextBundle::from_section(
+                            binding_text(target, &bindings, &capture),
+                            text_style(20.0),
+                        )
+                        .with_style(Style {
+                            width: Val::Px(320.0),
+                            ..default()
+                        }),
+                        BindingTextTag(target),
+                    ));
+
+                    // Keys and buttons are added to the ones already bound, clearing starts over.
+                    spawn_button(parent, ControlsButton::Rebind(target), "Add", row_button);
+                    spawn_button(parent, ControlsButton::Clear(target), "Clear", row_button);
+                });
+            }
+
+            if *page == ControlsPage::Actions {
+                for player in PlayerId::all(MAX_PLAYERS) {
+                    spawn_row(parent, |parent| {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                steering_text(player, &bindings),
+                                text_style(24.0),
+                            )
+                            .with_style(Style {
+                                width: Val::Px(430.0),
+                                ..default()
+                            }),
+                            SteeringTextTag(player),
+                        ));
+
+                        spawn_button(
+                            parent,
+                            ControlsButton::Steering(player),
+                            "Change",
+                            row_button,
+                        );
+                    });
+                }
+            }
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_button(parent, ControlsButton::Reset, "Reset", menu_button);
+
+                    let (other_page, text) = match *page {
+                        ControlsPage::Actions => (ControlsPage::Versus, "Versus keys"),
+                        ControlsPage::Versus => (ControlsPage::Actions, "Menu keys"),
+                    };
+                    spawn_button(parent, ControlsButton::Page(other_page), text, menu_button);
+
+                    spawn_button(parent, ControlsButton::Back, "Back", menu_button);
+                });
+        });
+}
+
+fn button_click(
+    query: Query<(&Interaction, &ControlsButtonTag), Changed<Interaction>>,
+    input: ActionInput,
+    mut capture: ResMut<BindingCapture>,
+    mut bindings: ResMut<InputBindings>,
+    mut page: ResMut<ControlsPage>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.just_pressed(InputAction::Back) {
+        next_state.set(GameState::StartMenu);
+    }
+
+    for (interaction, tag) in query.iter() {
+        if interaction == &Interaction::Pressed {
+            match tag.0 {
+                ControlsButton::Rebind(target) => {
+                    capture.target = Some(target);
+                    capture.conflict = None;
+                }
+                ControlsButton::Clear(target) => {
+                    bindings.clear(target);
+                    bindings.save();
+                }
+                ControlsButton::Steering(player) => {
+                    bindings.steering[player.0] = bindings.steering[player.0].next();
+                    bindings.save();
+                }
+                ControlsButton::Page(other_page) => *page = other_page,
+                ControlsButton::Reset => {
+                    *bindings = InputBindings::default();
+                    bindings.save();
+                }
+                ControlsButton::Back => next_state.set(GameState::StartMenu),
+            }
+        }
+    }
+}
+
+// Runs last, so the key that finishes the capture isn't also read as an action this frame.
+fn capture_binding(
+    mut capture: ResMut<BindingCapture>,
+    mut bindings: ResMut<InputBindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    button_inputs: Res<Input<GamepadButton>>,
+) {
+    // Skip the frame the capture started on, the key that started it is still pressed.
+    if capture.is_changed() {
+        return;
+    }
+
+    let Some(target) = capture.target else {
+        return;
+    };
+
+    let key = keyboard_input.get_just_pressed().next().copied();
+    let button = match target {
+        BindingTarget::Action(action) => button_inputs
+            .get_just_pressed()
+            .next()
+            .map(|button| (action, button.button_type)),
+        // Versus keys are only for the shared keyboard.
+        BindingTarget::Versus(..) => None,
+    };
+
+    // A key or button that would also trigger another action is refused.
+    let conflict = if let Some(key) = key {
+        bindings.key_conflict(target, key)
+    } else if let Some((action, button)) = button {
+        bindings.button_conflict(action, button)
+    } else {
+        return;
+    };
+
+    if conflict.is_some() {
+        capture.conflict = conflict;
+        return;
+    }
+
+    if let Some(key) = key {
+        bindings.add_key(target, key);
+    } else if let Some((action, button)) = button {
+        bindings.add_button(action, button);
+    }
+
+    bindings.save();
+    capture.target = None;
+    capture.conflict = None;
+}
+
+fn update_binding_texts(
+    mut query: Query<(&mut Text, &BindingTextTag)>,
+    mut steering_texts: Query<(&mut Text, &SteeringTextTag), Without<BindingTextTag>>,
+    bindings: Res<InputBindings>,
+    capture: Res<BindingCapture>,
+) {
+    if !bindings.is_changed() && !capture.is_changed() {
+        return;
+    }
+
+    for (mut text, tag) in query.iter_mut() {
+        text.sections[0].value = binding_text(tag.0, &bindings, &capture);
+    }
+
+    for (mut text, tag) in steering_texts.iter_mut() {
+        text.sections[0].value = steering_text(tag.0, &bindings);
+    }
+}
+
+fn stop_capture(mut capture: ResMut<BindingCapture>) {
+    *capture = BindingCapture::default();
+}
+
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ControlsPage::default())
+            .add_systems(OnEnter(GameState::ControlsMenu), reset_page)
+            .add_systems(
+                Update,
+                (
+                    (despawn_ui, create_menu)
+                        .chain()
+                        .run_if(resource_changed::<ControlsPage>()),
+                    button_click,
+                    update_binding_texts,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::ControlsMenu)),
+            )
+            .add_systems(
+                Last,
+                capture_binding.run_if(in_state(GameState::ControlsMenu)),
+            )
+            .add_systems(OnExit(GameState::ControlsMenu), (despawn_ui, stop_capture));
+    }
+}
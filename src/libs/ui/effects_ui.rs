@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+
+use crate::libs::{
+    eatables::powerups::{
+        active_effects::{ActiveEffect, ActiveEffects},
+        registry::PowerupRegistry,
+    },
+    game_configuration::GameConfiguration,
+    game_states::GameState,
+    player::PlayerId,
+    schedule::InGameSet,
+};
+
+const CHIP_SIZE: f32 = 24.0;
+const CHIP_BORDER: f32 = 3.0;
+const BAR_WIDTH: f32 = 48.0;
+const BAR_HEIGHT: f32 = 6.0;
+const BAR_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.6);
+
+#[derive(Component)]
+struct EffectsUiTag;
+
+// Strip in the top right corner, one colour chip with a countdown bar per active effect.
+// Despawned by `despawn_ui` together with the score when the game ends.
+fn spawn_effects_ui(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(8.0),
+                ..default()
+            },
+            ..default()
+        },
+        EffectsUiTag,
+    ));
+}
+
+fn update_effects_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<EffectsUiTag>>,
+    effects: Res<ActiveEffects>,
+    snake_effects: Query<(Ref<ActiveEffects>, &PlayerId)>,
+    registry: Res<PowerupRegistry>,
+    configuration: Res<GameConfiguration>,
+) {
+    // Effects only change on eat and on tick, so the strip is rebuilt just then.
+    if !effects.is_changed()
+        && !snake_effects
+            .iter()
+            .any(|(effects, _)| effects.is_changed())
+    {
+        return;
+    }
+
+    let Ok(strip) = query.get_single() else {
+        return;
+    };
+
+    // Board wide effects, then the players' own ones in their colour. Bots' effects aren't shown.
+    let mut chips: Vec<(ActiveEffect, Option<Color>)> = effects
+        .iter()
+        .map(|effect| (effect.clone(), None))
+        .collect();
+
+    for (snake_effects, player) in snake_effects.iter() {
+        if configuration.is_bot(*player) {
+            continue;
+        }
+
+        for effect in snake_effects.iter() {
+            // Frenzy runs in both, one chip is enough.
+            if !effects.is_active(effect.powerup) {
+                chips.push((effect.clone(), Some(player.head_color(&configuration))));
+            }
+        }
+    }
+
+    commands.entity(strip).despawn_descendants();
+
+    commands.entity(strip).with_children(|parent| {
+        for (effect, owner) in chips {
+            let remaining = effect.remaining_ticks as f32 / effect.duration.max(1) as f32;
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(CHIP_SIZE),
+                            height: Val::Px(CHIP_SIZE),
+                            border: UiRect::all(Val::Px(CHIP_BORDER)),
+                            ..default()
+                        },
+                        background_color: registry.entry(&effect.id).get_color().into(),
+                        border_color: BorderColor(owner.unwrap_or(Color::NONE)),
+                        ..default()
+                    });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(BAR_WIDTH),
+                                height: Val::Px(BAR_HEIGHT),
+                                ..default()
+                            },
+                            background_color: BAR_BACKGROUND_COLOR.into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Percent(remaining * 100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                background_color: registry.entry(&effect.id).get_color().into(),
+                                ..default()
+                            });
+                        });
+                });
+        }
+    });
+}
+
+pub struct EffectsUiPlugin;
+
+impl Plugin for EffectsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), spawn_effects_ui)
+            .add_systems(
+                Update,
+                update_effects_ui.in_set(InGameSet::GlobalPostionUpdates),
+            );
+    }
+}
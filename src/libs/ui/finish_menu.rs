@@ -1,10 +1,73 @@
 use bevy::prelude::*;
 
-use crate::libs::{game_states::GameState, score::Score};
+use crate::libs::{
+    death::{LastDeathCause, RoundWinner},
+    game_configuration::{GameConfiguration, GameMode},
+    game_states::GameState,
+    high_scores::HighScores,
+    player::PlayerId,
+    score::Score,
+};
 
-use super::ui_utils::despawn_ui;
+use super::{
+    text_input::{spawn_text_input, TextInput, TextInputSubmitEvent},
+    ui_utils::{despawn_ui, Focusable, BUTTON_COLOR},
+};
+
+const MAX_NAME_LEN: usize = 12;
+
+fn rank_message(high_scores: &HighScores, configuration: &GameConfiguration) -> String {
+    let difficulty = configuration.current_difficulty;
+
+    match high_scores.last_rank {
+        Some(rank) => format!("New high score! #{} on {:?}", rank + 1, difficulty),
+        None => {
+            let best = high_scores.table.get(difficulty).first();
+            format!(
+                "Best on {:?}: {}",
+                difficulty,
+                best.map_or(0, |entry| entry.score)
+            )
+        }
+    }
+}
+
+// Title and the line under it, versus rounds show the winner and everyone's points.
+fn result_texts(
+    score: &Score,
+    winner: &RoundWinner,
+    configuration: &GameConfiguration,
+) -> (String, String) {
+    match configuration.mode {
+        GameMode::Single => (
+            format!("Your score: {}", score.get(PlayerId::ONE)),
+            String::new(),
+        ),
+        GameMode::Versus => {
+            let title = match winner.0 {
+                Some(player) => format!("Player {} wins!", player.number()),
+                None => "Draw!".to_string(),
+            };
+
+            let scores: Vec<String> = PlayerId::all(configuration.mode.player_count())
+                .map(|player| format!("Player {}: {}", player.number(), score.get(player)))
+                .collect();
+
+            (title, scores.join("   "))
+        }
+    }
+}
+
+fn create_menu(
+    mut commands: Commands,
+    score: Res<Score>,
+    death_cause: Res<LastDeathCause>,
+    winner: Res<RoundWinner>,
+    high_scores: Res<HighScores>,
+    configuration: Res<GameConfiguration>,
+) {
+    let (title, scores) = result_texts(&score, &winner, &configuration);
 
-fn create_menu(mut commands: Commands, score: Res<Score>) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -19,7 +82,7 @@ fn create_menu(mut commands: Commands, score: Res<Score>) {
         })
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
-                format!("Your score: {}", score.0),
+                title,
                 TextStyle {
                     font_size: 80.0,
                     color: Color::rgb(0.9, 0.9, 0.9),
@@ -27,26 +90,73 @@ fn create_menu(mut commands: Commands, score: Res<Score>) {
                 },
             ));
 
+            if !scores.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    scores,
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ));
+            }
+
+            parent.spawn(TextBundle::from_section(
+                death_cause.0.get_message(),
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ));
+
+            if configuration.mode == GameMode::Single {
+                parent.spawn(TextBundle::from_section(
+                    rank_message(&high_scores, &configuration),
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ));
+            }
+
+            if high_scores.last_rank.is_some() {
+                parent.spawn(TextBundle::from_section(
+                    "Enter your name",
+                    TextStyle {
+                        font_size: 30.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ));
+
+                spawn_text_input(parent, MAX_NAME_LEN);
+            }
+
             parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        width: Val::Px(300.0),
-                        height: Val::Px(65.0),
-                        border: UiRect::all(Val::Px(5.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        margin: UiRect {
-                            left: Val::Percent(10.),
-                            right: Val::Percent(10.),
-                            top: Val::Percent(10.),
-                            bottom: Val::Percent(10.),
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(300.0),
+                            height: Val::Px(65.0),
+                            border: UiRect::all(Val::Px(5.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect {
+                                left: Val::Percent(10.),
+                                right: Val::Percent(10.),
+                                top: Val::Percent(10.),
+                                bottom: Val::Percent(10.),
+                            },
+                            ..default()
                         },
+                        border_color: BorderColor(Color::BLACK),
+                        background_color: BUTTON_COLOR.into(),
                         ..default()
                     },
-                    border_color: BorderColor(Color::BLACK),
-                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
-                    ..default()
-                })
+                    Focusable,
+                ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
                         "Main menu",
@@ -60,15 +170,30 @@ fn create_menu(mut commands: Commands, score: Res<Score>) {
         });
 }
 
+// Leaving with the button or by submitting the name keeps the typed name.
 fn button_click(
     query: Query<(&Interaction,), (Changed<Interaction>, With<Button>)>,
+    mut ev_submit: EventReader<TextInputSubmitEvent>,
+    name_input: Query<&TextInput>,
+    mut high_scores: ResMut<HighScores>,
+    configuration: Res<GameConfiguration>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for (interaction,) in query.iter() {
-        if interaction == &Interaction::Pressed {
-            next_state.set(GameState::StartMenu);
-        }
+    let clicked = query
+        .iter()
+        .any(|(interaction,)| interaction == &Interaction::Pressed);
+    let submitted = ev_submit.read().count() > 0;
+
+    if !clicked && !submitted {
+        return;
+    }
+
+    if let (Some(rank), Ok(name_input)) = (high_scores.last_rank, name_input.get_single()) {
+        let difficulty = configuration.current_difficulty;
+        high_scores.set_name(difficulty, rank, &name_input.value);
     }
+
+    next_state.set(GameState::StartMenu);
 }
 
 pub struct FinishMenuPlugin;
@@ -1,6 +1,9 @@
+pub mod controls_menu;
+pub mod effects_ui;
 pub mod finish_menu;
 pub mod pause_ui;
 pub mod score_ui;
 pub mod snake_ui_plugin;
 pub mod start_menu;
+pub mod text_input;
 pub mod ui_utils;
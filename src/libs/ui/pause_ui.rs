@@ -1,14 +1,72 @@
 use bevy::prelude::*;
 
-use crate::libs::schedule::InGameSet;
+use crate::libs::{
+    game_states::GameState,
+    input::{action_events::ActionPauseEvent, devices::LostDevices},
+    schedule::InGameSet,
+};
+
+use super::ui_utils::{Focusable, BUTTON_COLOR};
 
 #[derive(Component)]
 struct PausedUiTag;
 
+#[derive(Component)]
+struct LostDevicesTextTag;
+
+fn lost_devices_text(lost: &LostDevices) -> String {
+    match lost.0.as_slice() {
+        [] => String::new(),
+        [player] => format!("Controller of player {} disconnected", player + 1),
+        _ => "Controllers disconnected".to_string(),
+    }
+}
+
+enum PauseButton {
+    Resume,
+    MainMenu,
+}
+
+#[derive(Component)]
+struct PauseButtonTag(PauseButton);
+
+fn spawn_button(parent: &mut ChildBuilder, button: PauseButton, text: &str) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(300.0),
+                    height: Val::Px(65.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                border_color: BorderColor(Color::BLACK),
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+            PauseButtonTag(button),
+            Focusable,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ));
+        });
+}
+
 fn spaw_pause_ui(
     mut commands: Commands,
     query: Query<Entity, With<PausedUiTag>>,
     time: Res<Time<Virtual>>,
+    lost: Res<LostDevices>,
 ) {
     if time.is_paused() && query.is_empty() {
         commands
@@ -19,7 +77,7 @@ fn spaw_pause_ui(
                         height: Val::Percent(100.0),
                         align_items: AlignItems::Center,
                         justify_content: JustifyContent::Center,
-                        flex_direction: FlexDirection::Row,
+                        flex_direction: FlexDirection::Column,
                         ..default()
                     },
                     ..default()
@@ -36,6 +94,21 @@ fn spaw_pause_ui(
                         ..default()
                     },
                 ));
+
+                parent.spawn((
+                    TextBundle::from_section(
+                        lost_devices_text(&lost),
+                        TextStyle {
+                            font_size: 30.0,
+                            color: Color::rgb(0.1, 0.1, 0.1),
+                            ..default()
+                        },
+                    ),
+                    LostDevicesTextTag,
+                ));
+
+                spawn_button(parent, PauseButton::Resume, "Resume");
+                spawn_button(parent, PauseButton::MainMenu, "Main menu");
             });
     }
 }
@@ -52,11 +125,49 @@ fn despawn_pause_ui(
     }
 }
 
+fn update_lost_devices_text(
+    mut query: Query<&mut Text, With<LostDevicesTextTag>>,
+    lost: Res<LostDevices>,
+) {
+    if !lost.is_changed() {
+        return;
+    }
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = lost_devices_text(&lost);
+    }
+}
+
+fn button_click(
+    query: Query<(&Interaction, &PauseButtonTag), Changed<Interaction>>,
+    mut ev_action_pause: EventWriter<ActionPauseEvent>,
+    mut time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, tag) in query.iter() {
+        if interaction == &Interaction::Pressed {
+            match tag.0 {
+                // Goes through the pause action, so replays see it too.
+                PauseButton::Resume => ev_action_pause.send(ActionPauseEvent),
+                PauseButton::MainMenu => {
+                    time.unpause();
+                    next_state.set(GameState::StartMenu);
+                }
+            }
+        }
+    }
+}
+
 pub struct PauseUiPlugin;
 
 impl Plugin for PauseUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, spaw_pause_ui.in_set(InGameSet::UserInput))
-            .add_systems(Update, despawn_pause_ui.in_set(InGameSet::UserInput));
+            .add_systems(Update, despawn_pause_ui.in_set(InGameSet::UserInput))
+            .add_systems(
+                Update,
+                update_lost_devices_text.in_set(InGameSet::UserInput),
+            )
+            .add_systems(Update, button_click.in_set(InGameSet::UserInput));
     }
 }
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::libs::{game_states::GameState, schedule::InGameSet, score::Score};
+use crate::libs::{
+    game_configuration::GameConfiguration, game_states::GameState, player::PlayerId,
+    schedule::InGameSet, score::Score,
+};
 
 use super::ui_utils::despawn_ui;
 
@@ -11,7 +14,7 @@ fn spawn_score_ui(mut commands: Commands) {
     commands
         .spawn(NodeBundle {
             style: Style {
-                width: Val::Percent(8.0),
+                min_width: Val::Percent(8.0),
                 height: Val::Percent(8.0),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
@@ -35,10 +38,18 @@ fn spawn_score_ui(mut commands: Commands) {
         });
 }
 
-fn update_score_ui(mut query: Query<&mut Text, With<ScoreUiTextTag>>, score: Res<Score>) {
+fn update_score_ui(
+    mut query: Query<&mut Text, With<ScoreUiTextTag>>,
+    score: Res<Score>,
+    configuration: Res<GameConfiguration>,
+) {
     if score.is_changed() {
         let mut text = query.single_mut();
-        text.sections[0].value = score.0.to_string();
+        let scores: Vec<String> = PlayerId::all(configuration.mode.player_count())
+            .map(|player| score.get(player).to_string())
+            .collect();
+
+        text.sections[0].value = scores.join(" : ");
     }
 }
 
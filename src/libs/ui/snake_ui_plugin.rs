@@ -1,8 +1,9 @@
 use bevy::app::{PluginGroup, PluginGroupBuilder};
 
 use super::{
-    finish_menu::FinishMenuPlugin, pause_ui::PauseUiPlugin, score_ui::ScoreUiPlugin,
-    start_menu::StartMenuPlugin,
+    controls_menu::ControlsMenuPlugin, effects_ui::EffectsUiPlugin, finish_menu::FinishMenuPlugin,
+    pause_ui::PauseUiPlugin, score_ui::ScoreUiPlugin, start_menu::StartMenuPlugin,
+    text_input::TextInputPlugin, ui_utils::MenuFocusPlugin,
 };
 
 pub struct SnakeUiPlugins;
@@ -12,7 +13,11 @@ impl PluginGroup for SnakeUiPlugins {
         PluginGroupBuilder::start::<Self>()
             .add(StartMenuPlugin)
             .add(ScoreUiPlugin)
+            .add(EffectsUiPlugin)
             .add(PauseUiPlugin)
             .add(FinishMenuPlugin)
+            .add(ControlsMenuPlugin)
+            .add(TextInputPlugin)
+            .add(MenuFocusPlugin)
     }
 }
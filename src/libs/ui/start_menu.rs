@@ -1,17 +1,33 @@
 use bevy::prelude::*;
 
 use crate::libs::{
-    game_configuration::{self, GameConfiguration},
+    game_configuration::{self, BoundaryMode, GameConfiguration, GameMode, GridPreset},
     game_states::GameState,
+    level::{level_name, Level, LevelHandles, LEVEL_FILES},
+    player::MAX_BOTS,
+    sim::bot::BotLevel,
 };
 
-use super::ui_utils::despawn_ui;
+use super::ui_utils::{despawn_ui, Focusable, BUTTON_COLOR};
+
+const SELECTED_BORDER_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
 
 enum ButtonType {
     Easy,
     Medium,
     Hard,
     Extreme,
+    Grid(GridPreset),
+    Boundary(BoundaryMode),
+    Mode(GameMode),
+    // Cycle through the number of bots and how strong they are.
+    Bots,
+    BotLevel,
+    // Index into `LEVEL_FILES`.
+    Level(usize),
+    SmoothMovement,
+    SnakeSkin,
+    Controls,
 }
 
 #[derive(Component)]
@@ -21,6 +37,7 @@ struct ButtonTag(ButtonType);
 struct TaggedButtonBundle {
     button_tag: ButtonTag,
     button_bundle: ButtonBundle,
+    focusable: Focusable,
 }
 
 impl TaggedButtonBundle {
@@ -48,9 +65,10 @@ impl TaggedButtonBundle {
                         ..default()
                     },
                     border_color: BorderColor(Color::BLACK),
-                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    background_color: BUTTON_COLOR.into(),
                     ..default()
                 },
+                focusable: Focusable,
             })
             .with_children(|parent| {
                 parent.spawn(TextBundle::from_section(
@@ -65,7 +83,34 @@ impl TaggedButtonBundle {
     }
 }
 
-fn create_menu(mut commands: Commands) {
+fn bots_text(configuration: &GameConfiguration) -> String {
+    format!("Bots: {}", configuration.bots)
+}
+
+fn bot_level_text(configuration: &GameConfiguration) -> String {
+    let level = match configuration.bot_level {
+        BotLevel::Random => "Random",
+        BotLevel::Greedy => "Greedy",
+        BotLevel::Pathfinding => "Smart",
+    };
+
+    format!("{level} bots")
+}
+
+fn button_row() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Row,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+fn create_menu(mut commands: Commands, game_configuration: Res<GameConfiguration>) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -73,23 +118,127 @@ fn create_menu(mut commands: Commands) {
                 height: Val::Percent(100.0),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
-                flex_direction: FlexDirection::Row,
+                flex_direction: FlexDirection::Column,
                 ..default()
             },
             ..default()
         })
         .with_children(|parent| {
-            TaggedButtonBundle::create_new(parent, ButtonType::Easy, "Easy");
-            TaggedButtonBundle::create_new(parent, ButtonType::Medium, "Medium");
-            TaggedButtonBundle::create_new(parent, ButtonType::Hard, "Hard");
-            TaggedButtonBundle::create_new(parent, ButtonType::Extreme, "Extreme");
+            parent.spawn(button_row()).with_children(|parent| {
+                TaggedButtonBundle::create_new(parent, ButtonType::Easy, "Easy");
+                TaggedButtonBundle::create_new(parent, ButtonType::Medium, "Medium");
+                TaggedButtonBundle::create_new(parent, ButtonType::Hard, "Hard");
+                TaggedButtonBundle::create_new(parent, ButtonType::Extreme, "Extreme");
+            });
+
+            parent.spawn(button_row()).with_children(|parent| {
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Grid(GridPreset::Small),
+                    "Small",
+                );
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Grid(GridPreset::Classic),
+                    "Classic",
+                );
+                TaggedButtonBundle::create_new(parent, ButtonType::Grid(GridPreset::Wide), "Wide");
+            });
+
+            parent.spawn(button_row()).with_children(|parent| {
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Boundary(BoundaryMode::Wrap),
+                    "Wrap",
+                );
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Boundary(BoundaryMode::Solid),
+                    "Walls",
+                );
+            });
+
+            parent.spawn(button_row()).with_children(|parent| {
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Mode(GameMode::Single),
+                    "1 Player",
+                );
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Mode(GameMode::Versus),
+                    "Versus",
+                );
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::Bots,
+                    bots_text(&game_configuration),
+                );
+                TaggedButtonBundle::create_new(
+                    parent,
+                    ButtonType::BotLevel,
+                    bot_level_text(&game_configuration),
+                );
+            });
+
+            parent.spawn(button_row()).with_children(|parent| {
+                for (id, path) in LEVEL_FILES.iter().enumerate() {
+                    let mut name = level_name(path);
+                    name[..1].make_ascii_uppercase();
+
+                    TaggedButtonBundle::create_new(parent, ButtonType::Level(id), name);
+                }
+            });
+
+            parent.spawn(button_row()).with_children(|parent| {
+                TaggedButtonBundle::create_new(parent, ButtonType::SmoothMovement, "Smooth");
+                TaggedButtonBundle::create_new(parent, ButtonType::SnakeSkin, "Skin");
+                TaggedButtonBundle::create_new(parent, ButtonType::Controls, "Controls");
+            });
         });
 }
 
+fn highlight_selected_options(
+    mut query: Query<(&ButtonTag, &mut BorderColor)>,
+    game_configuration: Res<GameConfiguration>,
+) {
+    for (tag, mut border_color) in query.iter_mut() {
+        let selected = match tag.0 {
+            ButtonType::Grid(preset) => {
+                let (width, height, cell_size) = preset.get_dimensions();
+
+                game_configuration.level.is_none()
+                    && game_configuration.grid_width == width
+                    && game_configuration.grid_height == height
+                    && game_configuration.cell_size == cell_size
+            }
+            ButtonType::Boundary(boundary_mode) => {
+                game_configuration.boundary_mode == boundary_mode
+            }
+            ButtonType::Mode(mode) => game_configuration.mode == mode,
+            ButtonType::Level(id) => game_configuration
+                .level
+                .as_ref()
+                .is_some_and(|level| level.name == level_name(LEVEL_FILES[id])),
+            ButtonType::SmoothMovement => game_configuration.smooth_movement,
+            ButtonType::SnakeSkin => game_configuration.snake_skin,
+            _ => continue,
+        };
+
+        border_color.0 = if selected {
+            SELECTED_BORDER_COLOR
+        } else {
+            Color::BLACK
+        };
+    }
+}
+
 fn button_click(
     query: Query<(&Interaction, &ButtonTag), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<GameState>>,
     mut game_configuration: ResMut<GameConfiguration>,
+    levels: Res<Assets<Level>>,
+    level_handles: Res<LevelHandles>,
 ) {
     for (interaction, tag) in query.iter() {
         if interaction == &Interaction::Pressed {
@@ -111,18 +260,90 @@ fn button_click(
                         game_configuration::GameDifficulty::Extreme,
                     );
                 }
+                // Board options don't start the game, a difficulty pick does.
+                ButtonType::Grid(preset) => {
+                    game_configuration.set_grid_preset(preset);
+                    continue;
+                }
+                ButtonType::Boundary(boundary_mode) => {
+                    game_configuration.boundary_mode = boundary_mode;
+                    continue;
+                }
+                ButtonType::Mode(mode) => {
+                    game_configuration.mode = mode;
+                    continue;
+                }
+                ButtonType::Bots => {
+                    game_configuration.bots = (game_configuration.bots + 1) % (MAX_BOTS + 1);
+                    continue;
+                }
+                ButtonType::BotLevel => {
+                    game_configuration.bot_level = game_configuration.bot_level.next();
+                    continue;
+                }
+                ButtonType::Level(id) => {
+                    match level_handles
+                        .0
+                        .get(id)
+                        .and_then(|handle| levels.get(handle))
+                    {
+                        Some(level) => game_configuration.set_level(level.clone()),
+                        None => warn!("Level {} is not loaded", LEVEL_FILES[id]),
+                    }
+                    continue;
+                }
+                ButtonType::SmoothMovement => {
+                    game_configuration.smooth_movement = !game_configuration.smooth_movement;
+                    continue;
+                }
+                ButtonType::SnakeSkin => {
+                    game_configuration.snake_skin = !game_configuration.snake_skin;
+                    continue;
+                }
+                ButtonType::Controls => {
+                    next_state.set(GameState::ControlsMenu);
+                    continue;
+                }
             }
             next_state.set(GameState::InGame);
         }
     }
 }
 
+fn update_bot_buttons(
+    query: Query<(&ButtonTag, &Children)>,
+    mut texts: Query<&mut Text>,
+    game_configuration: Res<GameConfiguration>,
+) {
+    if !game_configuration.is_changed() {
+        return;
+    }
+
+    for (tag, children) in query.iter() {
+        let value = match tag.0 {
+            ButtonType::Bots => bots_text(&game_configuration),
+            ButtonType::BotLevel => bot_level_text(&game_configuration),
+            _ => continue,
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+}
+
 pub struct StartMenuPlugin;
 
 impl Plugin for StartMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::StartMenu), create_menu)
-            .add_systems(Update, button_click.run_if(in_state(GameState::StartMenu)))
+            .add_systems(
+                Update,
+                (button_click, highlight_selected_options, update_bot_buttons)
+                    .run_if(in_state(GameState::StartMenu)),
+            )
             .add_systems(OnExit(GameState::StartMenu), despawn_ui);
     }
 }
@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+
+use crate::libs::input::bindings::{ActionInput, InputAction};
+
+// Letters a gamepad cycles through, arcade style.
+pub const ARCADE_LETTERS: [char; 37] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ' ',
+];
+
+const TEXT_INPUT_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+const TEXT_INPUT_BORDER_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+
+// Single line text field. Typed on the keyboard, or with a gamepad:
+// up/down picks the letter under the cursor, left/right moves the cursor,
+// back deletes the last letter and confirm submits.
+#[derive(Component, Default)]
+pub struct TextInput {
+    pub value: String,
+    pub max_len: usize,
+    // Letter the gamepad edits, `value.len()` means a new one.
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len: max_len.max(1),
+            ..default()
+        }
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn push(&mut self, letter: char) {
+        if self.value.chars().count() < self.max_len {
+            self.value.push(letter);
+        }
+
+        self.cursor = self.value.chars().count().min(self.max_len - 1);
+    }
+
+    pub fn pop(&mut self) {
+        self.value.pop();
+        self.cursor = self.value.chars().count();
+    }
+
+    // Moves the letter under the cursor `step` places through `ARCADE_LETTERS`.
+    pub fn cycle_letter(&mut self, step: isize) {
+        let mut letters: Vec<char> = self.value.chars().collect();
+
+        if self.cursor == letters.len() {
+            letters.push(ARCADE_LETTERS[ARCADE_LETTERS.len() - 1]);
+        }
+
+        let current = ARCADE_LETTERS
+            .iter()
+            .position(|letter| *letter == letters[self.cursor].to_ascii_uppercase())
+            .unwrap_or(0) as isize;
+        let next = (current + step).rem_euclid(ARCADE_LETTERS.len() as isize);
+        letters[self.cursor] = ARCADE_LETTERS[next as usize];
+
+        self.value = letters.into_iter().collect();
+    }
+
+    pub fn move_cursor(&mut self, step: isize) {
+        let len = self.value.chars().count();
+        let last = len.min(self.max_len - 1);
+
+        self.cursor = (self.cursor as isize + step).clamp(0, last as isize) as usize;
+    }
+
+    // Text shown in the field, `_` or brackets mark where the gamepad cursor is.
+    pub fn display(&self) -> String {
+        let mut text = String::new();
+
+        for (id, letter) in self.value.chars().enumerate() {
+            if id == self.cursor {
+                text.push_str(&format!("[{letter}]"));
+            } else {
+                text.push(letter);
+            }
+        }
+
+        if self.cursor == self.value.chars().count() {
+            text.push('_');
+        }
+
+        text
+    }
+}
+
+#[derive(Component)]
+struct TextInputText;
+
+#[derive(Event)]
+pub struct TextInputSubmitEvent {
+    pub entity: Entity,
+    pub value: String,
+}
+
+pub fn spawn_text_input(parent: &mut ChildBuilder, max_len: usize) -> Entity {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    min_width: Val::Px(300.0),
+                    height: Val::Px(65.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                border_color: BorderColor(TEXT_INPUT_BORDER_COLOR),
+                background_color: TEXT_INPUT_COLOR.into(),
+                ..default()
+            },
+            TextInput::new(max_len),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "_",
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ),
+                TextInputText,
+            ));
+        })
+        .id()
+}
+
+fn type_text(
+    mut ev_character: EventReader<ReceivedCharacter>,
+    keyboard_input: Res<Input<KeyCode>>,
+    input: ActionInput,
+    mut query: Query<(Entity, &mut TextInput)>,
+    mut ev_submit: EventWriter<TextInputSubmitEvent>,
+) {
+    let characters: Vec<char> = ev_character
+        .read()
+        .map(|ev| ev.char)
+        .filter(|char| !char.is_control())
+        .collect();
+
+    for (entity, mut text_input) in query.iter_mut() {
+        for char in characters.iter() {
+            text_input.push(*char);
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Back) {
+            text_input.pop();
+        }
+
+        if input.non_text_key_just_pressed(InputAction::Confirm) {
+            ev_submit.send(TextInputSubmitEvent {
+                entity,
+                value: text_input.value.clone(),
+            });
+        }
+    }
+}
+
+fn pick_letters(
+    input: ActionInput,
+    mut query: Query<(Entity, &mut TextInput)>,
+    mut ev_submit: EventWriter<TextInputSubmitEvent>,
+) {
+    for (entity, mut text_input) in query.iter_mut() {
+        if input.any_button_just_pressed(InputAction::Up) {
+            text_input.cycle_letter(-1);
+        }
+
+        if input.any_button_just_pressed(InputAction::Down) {
+            text_input.cycle_letter(1);
+        }
+
+        if input.any_button_just_pressed(InputAction::Left) {
+            text_input.move_cursor(-1);
+        }
+
+        if input.any_button_just_pressed(InputAction::Right) {
+            text_input.move_cursor(1);
+        }
+
+        if input.any_button_just_pressed(InputAction::Back) {
+            text_input.pop();
+        }
+
+        if input.any_button_just_pressed(InputAction::Confirm) {
+            ev_submit.send(TextInputSubmitEvent {
+                entity,
+                value: text_input.value.clone(),
+            });
+        }
+    }
+}
+
+fn render_text_input(
+    query: Query<(&TextInput, &Children), Changed<TextInput>>,
+    mut texts: Query<&mut Text, With<TextInputText>>,
+) {
+    for (text_input, children) in query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = text_input.display();
+            }
+        }
+    }
+}
+
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TextInputSubmitEvent>()
+            .add_systems(Update, (type_text, pick_letters, render_text_input).chain());
+    }
+}
@@ -1,7 +1,197 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::UiSystem, utils::HashMap};
+
+use crate::libs::input::{
+    bindings::{ActionInput, InputAction},
+    direction::Direction,
+    stick::{left_stick, stick_direction},
+};
+
+use super::text_input::TextInput;
+
+pub const BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const FOCUSED_BUTTON_COLOR: Color = Color::rgb(0.35, 0.35, 0.35);
 
 pub fn despawn_ui(mut commands: Commands, query: Query<Entity, With<Node>>) {
     for menu in query.iter() {
         commands.entity(menu).despawn();
     }
 }
+
+// Buttons that can be reached with the keyboard or a gamepad.
+#[derive(Component)]
+pub struct Focusable;
+
+// Focused button of whatever menu is shown.
+#[derive(Resource, Default)]
+pub struct Focus(pub Option<Entity>);
+
+fn focus_direction(input: &ActionInput, typing: bool) -> Option<Direction> {
+    // While a text field is shown letters are typed, not used to move around.
+    let pressed = |action: InputAction| {
+        input.any_button_just_pressed(action)
+            || if typing {
+                input.non_text_key_just_pressed(action)
+            } else {
+                input.key_just_pressed(action)
+            }
+    };
+
+    [
+        (InputAction::Up, Direction::Up),
+        (InputAction::Down, Direction::Down),
+        (InputAction::Left, Direction::Left),
+        (InputAction::Right, Direction::Right),
+    ]
+    .into_iter()
+    .find(|(action, _)| pressed(*action))
+    .map(|(_, direction)| direction)
+}
+
+// UI coordinates grow to the right and down.
+fn ui_direction(direction: Direction) -> Vec2 {
+    match direction {
+        Direction::Up => Vec2::NEG_Y,
+        Direction::Down => Vec2::Y,
+        Direction::Left => Vec2::NEG_X,
+        Direction::Right => Vec2::X,
+    }
+}
+
+// Closest button in `direction`, buttons off to the side count as further away.
+fn next_focus(
+    current: Vec2,
+    direction: Vec2,
+    candidates: impl Iterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    candidates
+        .filter_map(|(entity, position)| {
+            let offset = position - current;
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+
+            (along > 0.0).then_some((entity, along + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn move_focus(
+    mut focus: ResMut<Focus>,
+    query: Query<(Entity, &GlobalTransform), With<Focusable>>,
+    text_inputs: Query<(), With<TextInput>>,
+    input: ActionInput,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    // Direction every gamepad's stick is held in since its last push.
+    mut held: Local<HashMap<Gamepad, Direction>>,
+) {
+    let position = |transform: &GlobalTransform| transform.translation().truncate();
+
+    // Menus take the stick of every gamepad whatever the steering mode, like the d-pad.
+    // Only a new push moves the focus, holding the stick doesn't repeat it.
+    let mut pushed = None;
+
+    for gamepad in gamepads.iter() {
+        let before = held.get(&gamepad).copied();
+        let direction =
+            stick_direction(before, left_stick(&axes, gamepad), &input.bindings().stick);
+
+        if direction != before {
+            pushed = pushed.or(direction);
+        }
+
+        match direction {
+            Some(direction) => held.insert(gamepad, direction),
+            None => held.remove(&gamepad),
+        };
+    }
+
+    // Start at the top left button whenever the focused one went away.
+    if focus.0.is_none_or(|entity| !query.contains(entity)) {
+        focus.0 = query
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                let (a, b) = (position(a), position(b));
+                a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+            })
+            .map(|(entity, _)| entity);
+    }
+
+    let Some((_, current)) = focus.0.and_then(|entity| query.get(entity).ok()) else {
+        return;
+    };
+
+    let Some(direction) = focus_direction(&input, !text_inputs.is_empty()).or(pushed) else {
+        return;
+    };
+
+    let candidates = query
+        .iter()
+        .map(|(entity, transform)| (entity, position(transform)));
+
+    if let Some(next) = next_focus(position(current), ui_direction(direction), candidates) {
+        focus.0 = Some(next);
+    }
+}
+
+// Presses the focused button the same way a mouse click does, so menus only
+// have to look at `Interaction`. The press is released again on the next frame.
+fn activate_focus(
+    focus: Res<Focus>,
+    mut query: Query<&mut Interaction, With<Focusable>>,
+    text_inputs: Query<(), With<TextInput>>,
+    input: ActionInput,
+    mut pressed: Local<Option<Entity>>,
+) {
+    if let Some(entity) = pressed.take()
+        && let Ok(mut interaction) = query.get_mut(entity)
+    {
+        interaction.set_if_neq(Interaction::None);
+    }
+
+    let activated = input.any_button_just_pressed(InputAction::Confirm)
+        || if text_inputs.is_empty() {
+            input.key_just_pressed(InputAction::Confirm)
+        } else {
+            input.non_text_key_just_pressed(InputAction::Confirm)
+        };
+
+    if activated
+        && let Some(entity) = focus.0
+        && let Ok(mut interaction) = query.get_mut(entity)
+    {
+        *interaction = Interaction::Pressed;
+        *pressed = Some(entity);
+    }
+}
+
+fn highlight_focus(
+    focus: Res<Focus>,
+    mut query: Query<(Entity, &mut BackgroundColor), With<Focusable>>,
+) {
+    for (entity, mut background_color) in query.iter_mut() {
+        let color = if focus.0 == Some(entity) {
+            FOCUSED_BUTTON_COLOR
+        } else {
+            BUTTON_COLOR
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+pub struct MenuFocusPlugin;
+
+impl Plugin for MenuFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Focus::default())
+            .add_systems(
+                PreUpdate,
+                (move_focus, activate_focus).chain().after(UiSystem::Focus),
+            )
+            .add_systems(Update, highlight_focus);
+    }
+}
@@ -1,10 +1,35 @@
+use std::{env, path::PathBuf};
+
 use bevy::math::Vec2;
 
-use super::globals::{GRID_CELL, LEFT_WINDOW_BORDER, TOP_WINDOW_BORDER};
+use super::game_configuration::GameConfiguration;
+
+pub fn grid_to_screen(grid_x: u32, grid_y: u32, configuration: &GameConfiguration) -> Vec2 {
+    let cell_size = configuration.cell_size;
+    let window_size = configuration.window_size();
 
-pub fn grid_to_screen(grid_x: u32, grid_y: u32) -> Vec2 {
     Vec2 {
-        x: LEFT_WINDOW_BORDER + GRID_CELL / 2. + GRID_CELL * grid_x as f32,
-        y: TOP_WINDOW_BORDER - GRID_CELL / 2. - GRID_CELL * grid_y as f32,
+        x: -window_size.x / 2. + cell_size / 2. + cell_size * grid_x as f32,
+        y: window_size.y / 2. - cell_size / 2. - cell_size * grid_y as f32,
     }
 }
+
+// Returns the value following `name` on the command line, e.g. `--seed 42`.
+pub fn cli_arg(name: &str) -> Option<String> {
+    env::args().skip_while(|arg| arg != name).nth(1)
+}
+
+// Per-user directory for the game's saved files, e.g. `~/.local/share/snake` on Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    base.map(|base| base.join("snake"))
+}
@@ -0,0 +1,137 @@
+use snake::libs::eatables::powerups::{
+    active_effects::{ActiveEffects, StackingPolicy},
+    powerup::Powerup,
+    registry::{PowerupEntry, PowerupRegistry},
+};
+
+// Built-in entry lasting 10 ticks.
+fn entry(powerup: Powerup, stacking: StackingPolicy) -> PowerupEntry {
+    PowerupEntry {
+        duration: 10,
+        stacking,
+        ..PowerupRegistry::default().get(powerup)
+    }
+}
+
+// Starts `entry`, lets 4 ticks pass and eats it again.
+fn eat_twice(entry: &PowerupEntry) -> ActiveEffects {
+    let mut effects = ActiveEffects::default();
+
+    effects.start(entry);
+    for _ in 0..4 {
+        effects.tick();
+    }
+    effects.start(entry);
+
+    effects
+}
+
+#[test]
+fn refresh_starts_the_duration_over() {
+    let effects = eat_twice(&entry(Powerup::Ghost, StackingPolicy::Refresh));
+
+    assert_eq!(effects.iter().count(), 1);
+    assert_eq!(effects.remaining_ticks(Powerup::Ghost), 10);
+}
+
+#[test]
+fn extend_adds_to_what_is_left() {
+    let effects = eat_twice(&entry(Powerup::Ghost, StackingPolicy::Extend));
+
+    assert_eq!(effects.iter().count(), 1);
+    assert_eq!(effects.remaining_ticks(Powerup::Ghost), 16);
+}
+
+#[test]
+fn stack_runs_another_instance() {
+    let effects = eat_twice(&entry(Powerup::Ghost, StackingPolicy::Stack));
+
+    let remaining: Vec<u32> = effects
+        .iter()
+        .map(|effect| effect.remaining_ticks)
+        .collect();
+    assert_eq!(remaining, [6, 10]);
+}
+
+#[test]
+fn ignore_keeps_the_running_effect() {
+    let effects = eat_twice(&entry(Powerup::Ghost, StackingPolicy::Ignore));
+
+    assert_eq!(effects.iter().count(), 1);
+    assert_eq!(effects.remaining_ticks(Powerup::Ghost), 6);
+}
+
+#[test]
+fn instant_powerups_start_no_effect() {
+    let mut effects = ActiveEffects::default();
+
+    effects.start(&PowerupRegistry::default().get(Powerup::Feast));
+
+    assert_eq!(effects.iter().count(), 0);
+}
+
+#[test]
+fn effects_end_when_their_ticks_run_out() {
+    let mut effects = ActiveEffects::default();
+    effects.start(&entry(Powerup::Ghost, StackingPolicy::Refresh));
+
+    for _ in 0..9 {
+        assert_eq!(effects.tick(), []);
+    }
+
+    assert_eq!(effects.tick(), [Powerup::Ghost]);
+    assert!(!effects.is_active(Powerup::Ghost));
+}
+
+#[test]
+fn stacked_effects_end_with_the_last_instance() {
+    let mut effects = eat_twice(&entry(Powerup::Ghost, StackingPolicy::Stack));
+
+    // The first instance runs out, the second one keeps the effect going.
+    for _ in 0..6 {
+        assert_eq!(effects.tick(), []);
+    }
+    assert!(effects.is_active(Powerup::Ghost));
+
+    for _ in 0..3 {
+        assert_eq!(effects.tick(), []);
+    }
+    assert_eq!(effects.tick(), [Powerup::Ghost]);
+}
+
+#[test]
+fn every_speed_effect_scales_the_tick_rate() {
+    let registry = PowerupRegistry::default();
+    let mut effects = eat_twice(&entry(Powerup::Slowdown, StackingPolicy::Stack));
+    effects.start(&entry(Powerup::Frenzy, StackingPolicy::Refresh));
+    effects.start(&entry(Powerup::Ghost, StackingPolicy::Refresh));
+
+    let slowdown = registry.get(Powerup::Slowdown).magnitude;
+    let frenzy = registry.get(Powerup::Frenzy).magnitude;
+
+    assert_eq!(
+        effects.tick_rate(0.1, &registry),
+        0.1 * slowdown * slowdown * frenzy
+    );
+    assert_eq!(ActiveEffects::default().tick_rate(0.1, &registry), 0.1);
+}
+
+#[test]
+fn largest_score_multiplier_counts() {
+    let registry = PowerupRegistry::from_ron(
+        r#"(powerups: [
+            (id: "frenzy", effect: Frenzy, color: (1.0, 0.5, 0.0), weight: 1.0, duration: 10, magnitude: 0.5, score_multiplier: 3),
+            (id: "double", effect: Ghost, color: (0.7, 0.5, 0.9), weight: 1.0, duration: 10, magnitude: 1.0, score_multiplier: 2),
+        ])"#,
+    )
+    .unwrap();
+    let mut effects = ActiveEffects::default();
+
+    assert_eq!(effects.score_multiplier(&registry), 1);
+
+    effects.start(&registry.entry("double"));
+    assert_eq!(effects.score_multiplier(&registry), 2);
+
+    effects.start(&registry.entry("frenzy"));
+    assert_eq!(effects.score_multiplier(&registry), 3);
+}
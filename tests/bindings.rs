@@ -0,0 +1,123 @@
+use bevy::prelude::{GamepadButtonType, KeyCode};
+use snake::libs::{
+    input::bindings::{BindingTarget, InputAction, InputBindings},
+    player::PlayerId,
+};
+
+#[test]
+fn default_bindings_have_no_conflicts() {
+    let bindings = InputBindings::default();
+
+    for action in InputAction::ALL {
+        let target = BindingTarget::Action(action);
+
+        for key in bindings.keys(target) {
+            assert_eq!(
+                bindings.key_conflict(target, *key),
+                None,
+                "{action:?} {key:?}"
+            );
+        }
+    }
+
+    for player in [PlayerId::ONE, PlayerId::TWO] {
+        for action in InputAction::STEERING {
+            let target = BindingTarget::Versus(player, action);
+
+            for key in bindings.keys(target) {
+                assert_eq!(
+                    bindings.key_conflict(target, *key),
+                    None,
+                    "{target:?} {key:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn keys_are_added_to_the_bound_ones() {
+    let mut bindings = InputBindings::default();
+    let up = BindingTarget::Action(InputAction::Up);
+
+    bindings.add_key(up, KeyCode::I);
+    bindings.add_key(up, KeyCode::I);
+
+    assert_eq!(bindings.keys(up), &[KeyCode::Up, KeyCode::W, KeyCode::I]);
+}
+
+#[test]
+fn cleared_targets_have_no_keys() {
+    let mut bindings = InputBindings::default();
+    let target = BindingTarget::Versus(PlayerId::ONE, InputAction::Left);
+
+    bindings.clear(target);
+    bindings.add_key(target, KeyCode::J);
+
+    assert_eq!(bindings.keys(target), &[KeyCode::J]);
+}
+
+#[test]
+fn keys_read_together_conflict() {
+    let bindings = InputBindings::default();
+
+    assert_eq!(
+        bindings.key_conflict(BindingTarget::Action(InputAction::Up), KeyCode::Escape),
+        Some(BindingTarget::Action(InputAction::Pause))
+    );
+    assert_eq!(
+        bindings.key_conflict(BindingTarget::Action(InputAction::Confirm), KeyCode::Escape),
+        Some(BindingTarget::Action(InputAction::Back))
+    );
+    assert_eq!(
+        bindings.key_conflict(
+            BindingTarget::Versus(PlayerId::ONE, InputAction::Up),
+            KeyCode::Up
+        ),
+        Some(BindingTarget::Versus(PlayerId::TWO, InputAction::Up))
+    );
+    assert_eq!(
+        bindings.key_conflict(
+            BindingTarget::Versus(PlayerId::TWO, InputAction::Down),
+            KeyCode::Escape
+        ),
+        Some(BindingTarget::Action(InputAction::Pause))
+    );
+}
+
+#[test]
+fn keys_never_read_together_dont_conflict() {
+    let bindings = InputBindings::default();
+
+    // Pause is only read in game, Back only in menus.
+    assert_eq!(
+        bindings.key_conflict(BindingTarget::Action(InputAction::Pause), KeyCode::Escape),
+        None
+    );
+    // Versus keys steer instead of the action keys.
+    assert_eq!(
+        bindings.key_conflict(
+            BindingTarget::Versus(PlayerId::ONE, InputAction::Up),
+            KeyCode::Return
+        ),
+        None
+    );
+    assert_eq!(
+        bindings.key_conflict(BindingTarget::Action(InputAction::Left), KeyCode::J),
+        None
+    );
+}
+
+#[test]
+fn buttons_read_together_conflict() {
+    let bindings = InputBindings::default();
+
+    assert_eq!(
+        bindings.button_conflict(InputAction::Left, GamepadButtonType::South),
+        Some(BindingTarget::Action(InputAction::Confirm))
+    );
+    assert_eq!(
+        bindings.button_conflict(InputAction::Pause, GamepadButtonType::East),
+        None
+    );
+}
@@ -0,0 +1,296 @@
+// Drives the game `App` without a window, audio or rendering.
+// Every test binary only uses part of the helpers.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::{
+    app::PluginGroup,
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo},
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use snake::libs::{
+    animation::AnimationHandles,
+    board::BoardPlugin,
+    bots::BotsPlugin,
+    cell::{Cell, CellBundle, CellPlugin},
+    death::{DeathCause, DeathPlugin, LastDeathCause, RoundWinner},
+    eatables::{
+        eat_event::EatEventPlugin,
+        food::{Food, FoodPlugin},
+        powerups::{
+            active_effects::ActiveEffects,
+            powerup::{Powerup, PowerupPlugins},
+            registry::{PowerupRegistry, PowerupRegistryPlugin},
+        },
+    },
+    game_configuration::{GameConfiguration, GameConfigurationPlugin},
+    game_rng::GameRngPlugin,
+    game_states::{GameStatatesPlugin, GameState},
+    input::{
+        action_events::{ActionEventsPlugin, ActionMoveEvent},
+        devices::{InputDevicesPlugin, LostDevices, PlayerDevices},
+        direction::Direction,
+    },
+    pause::GamePausePlugin,
+    player::PlayerId,
+    replay::replay_plugin::ReplayPlugins,
+    schedule::GameSchedulePlugin,
+    score::{Score, ScorePlugin},
+    snake::{Snake, SnakePlugin},
+};
+
+pub const SEED: u64 = 42;
+
+pub struct TestGame {
+    pub app: App,
+}
+
+impl TestGame {
+    pub fn new() -> Self {
+        Self::with_configuration(|_| {})
+    }
+
+    // Builds the app, `setup` can change the configuration before the game starts.
+    pub fn with_configuration(setup: impl FnOnce(&mut GameConfiguration)) -> Self {
+        let mut app = App::new();
+
+        // Time only moves in `advance_ticks`.
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+            .init_resource::<AnimationHandles>()
+            // The powerups file is an asset, the built-in table is used instead.
+            .insert_resource(PowerupRegistry::default())
+            .add_plugins(GameSchedulePlugin)
+            .add_plugins(GameStatatesPlugin)
+            .add_plugins(EatEventPlugin)
+            .add_plugins(GameConfigurationPlugin)
+            .add_plugins(GameRngPlugin)
+            .add_plugins(ActionEventsPlugin)
+            // Gamepads come and go through `connect_gamepad` and `disconnect_gamepad`.
+            .add_event::<GamepadConnectionEvent>()
+            .add_plugins(InputDevicesPlugin)
+            .add_plugins(ScorePlugin)
+            .add_plugins(CellPlugin)
+            .add_plugins(BoardPlugin)
+            .add_plugins(DeathPlugin)
+            .add_plugins(BotsPlugin)
+            .add_plugins(FoodPlugin)
+            .add_plugins(SnakePlugin)
+            .add_plugins(GamePausePlugin)
+            .add_plugins(PowerupPlugins.build().disable::<PowerupRegistryPlugin>())
+            .add_plugins(ReplayPlugins);
+
+        app.finish();
+        app.cleanup();
+
+        // Slow ticks would otherwise be cut to the default maximum frame time.
+        app.world
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(Duration::from_secs(60));
+
+        let mut configuration = app.world.resource_mut::<GameConfiguration>();
+        configuration.seed = Some(SEED);
+        setup(&mut configuration);
+
+        let mut game = Self { app };
+        game.app.update();
+
+        game
+    }
+
+    // Enters `GameState::InGame`, spawning the snakes, board and first food.
+    pub fn start(&mut self) {
+        self.set_state(GameState::InGame);
+    }
+
+    pub fn set_state(&mut self, state: GameState) {
+        self.app
+            .world
+            .resource_mut::<NextState<GameState>>()
+            .set(state);
+        self.app.update();
+    }
+
+    pub fn state(&self) -> GameState {
+        *self.app.world.resource::<State<GameState>>().get()
+    }
+
+    // Queued like a key press, applied on the next tick.
+    pub fn send_move(&mut self, player: PlayerId, direction: Direction) {
+        self.app
+            .world
+            .send_event(ActionMoveEvent { player, direction });
+    }
+
+    // Runs a frame lasting exactly until `tick_timer` finishes, then a frame without
+    // any time passing so events sent late in the tick (eating, deaths) are handled too.
+    pub fn advance_ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            let remaining = self
+                .app
+                .world
+                .resource::<GameConfiguration>()
+                .tick_timer
+                .remaining();
+
+            self.app
+                .insert_resource(TimeUpdateStrategy::ManualDuration(remaining));
+            self.app.update();
+
+            self.app
+                .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+            self.app.update();
+        }
+    }
+
+    // Runs a single frame covering `fraction` of a tick, e.g. to look at smooth movement mid-tick.
+    pub fn advance_fraction(&mut self, fraction: f32) {
+        let duration = self
+            .app
+            .world
+            .resource::<GameConfiguration>()
+            .tick_timer
+            .duration()
+            .mul_f32(fraction);
+
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(duration));
+        self.app.update();
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    }
+
+    // Plugs in gamepad `id` and runs a frame so it gets handed to a player.
+    pub fn connect_gamepad(&mut self, id: usize) {
+        let info = GamepadInfo {
+            name: format!("Gamepad {id}"),
+        };
+
+        self.send_connection(id, GamepadConnection::Connected(info));
+    }
+
+    pub fn disconnect_gamepad(&mut self, id: usize) {
+        self.send_connection(id, GamepadConnection::Disconnected);
+    }
+
+    fn send_connection(&mut self, id: usize, connection: GamepadConnection) {
+        self.app.world.send_event(GamepadConnectionEvent {
+            gamepad: Gamepad::new(id),
+            connection,
+        });
+        self.app.update();
+    }
+
+    // Gamepad of every player, `None` for players without one.
+    pub fn devices(&self) -> Vec<Option<Gamepad>> {
+        self.app.world.resource::<PlayerDevices>().players.clone()
+    }
+
+    // Players whose gamepad went away during the current game.
+    pub fn lost_devices(&self) -> Vec<usize> {
+        self.app.world.resource::<LostDevices>().0.clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.app.world.resource::<Time<Virtual>>().is_paused()
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.app.world.resource::<GameConfiguration>().tick
+    }
+
+    // Parts of a snake's body, head first. Empty once the snake is gone.
+    pub fn snake_cells(&mut self, player: PlayerId) -> Vec<Cell> {
+        let parts = self
+            .app
+            .world
+            .query::<(&Snake, &PlayerId)>()
+            .iter(&self.app.world)
+            .find(|(_, id)| **id == player)
+            .map(|(snake, _)| snake.parts.clone())
+            .unwrap_or_default();
+
+        parts
+            .iter()
+            .filter_map(|part| self.app.world.get::<Cell>(*part).copied())
+            .collect()
+    }
+
+    pub fn head(&mut self, player: PlayerId) -> Cell {
+        self.snake_cells(player)[0]
+    }
+
+    // Where the head's sprite is drawn.
+    pub fn head_translation(&mut self, player: PlayerId) -> Vec3 {
+        self.app
+            .world
+            .query::<(&Snake, &PlayerId, &Transform)>()
+            .iter(&self.app.world)
+            .find(|(_, id, _)| **id == player)
+            .map(|(_, _, transform)| transform.translation)
+            .unwrap()
+    }
+
+    // Effects running for this player's snake only.
+    pub fn effects(&mut self, player: PlayerId) -> ActiveEffects {
+        self.app
+            .world
+            .query::<(&ActiveEffects, &PlayerId)>()
+            .iter(&self.app.world)
+            .find(|(_, id)| **id == player)
+            .map(|(effects, _)| effects.clone())
+            .unwrap()
+    }
+
+    pub fn score(&self, player: PlayerId) -> u32 {
+        self.app.world.resource::<Score>().get(player)
+    }
+
+    pub fn last_death_cause(&self) -> DeathCause {
+        self.app.world.resource::<LastDeathCause>().0
+    }
+
+    pub fn winner(&self) -> Option<PlayerId> {
+        self.app.world.resource::<RoundWinner>().0
+    }
+
+    pub fn food_cells(&mut self) -> Vec<Cell> {
+        self.app
+            .world
+            .query_filtered::<&Cell, With<Food>>()
+            .iter(&self.app.world)
+            .copied()
+            .collect()
+    }
+
+    // Removes the randomly placed food so tests can place their own.
+    pub fn clear_food(&mut self) {
+        let foods: Vec<Entity> = self
+            .app
+            .world
+            .query_filtered::<Entity, With<Food>>()
+            .iter(&self.app.world)
+            .collect();
+
+        for food in foods {
+            self.app.world.despawn(food);
+        }
+    }
+
+    pub fn place_food(&mut self, cell: Cell, powerup: Powerup) {
+        let configuration = self.app.world.resource::<GameConfiguration>();
+        let entry = self.app.world.resource::<PowerupRegistry>().get(powerup);
+        let bundle = (
+            Food(entry.id.clone()),
+            CellBundle::new(cell, entry.get_color(), configuration),
+        );
+
+        self.app.world.spawn(bundle);
+    }
+}
+
+pub fn cell(x: u32, y: u32) -> Cell {
+    Cell { x, y }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use bevy::prelude::Gamepad;
+use common::TestGame;
+use snake::libs::{
+    game_configuration::GameMode, game_states::GameState, input::devices::PlayerDevices,
+};
+
+fn pad(id: usize) -> Option<Gamepad> {
+    Some(Gamepad::new(id))
+}
+
+#[test]
+fn gamepads_go_to_the_first_player_without_one() {
+    let mut devices = PlayerDevices::default();
+
+    assert_eq!(devices.assign(Gamepad::new(3)), Some(0));
+    assert_eq!(devices.assign(Gamepad::new(5)), Some(1));
+    // Every player has one already.
+    assert_eq!(devices.assign(Gamepad::new(7)), None);
+    // Connecting twice keeps the player.
+    assert_eq!(devices.assign(Gamepad::new(5)), Some(1));
+
+    assert_eq!(devices.players, [pad(3), pad(5)]);
+}
+
+#[test]
+fn released_gamepads_free_their_player() {
+    let mut devices = PlayerDevices::default();
+    devices.assign(Gamepad::new(3));
+    devices.assign(Gamepad::new(5));
+
+    assert_eq!(devices.release(Gamepad::new(3)), Some(0));
+    assert_eq!(devices.release(Gamepad::new(3)), None);
+    assert_eq!(devices.get(0), None);
+
+    assert_eq!(devices.assign(Gamepad::new(7)), Some(0));
+    assert_eq!(devices.player_of(Gamepad::new(7)), Some(0));
+}
+
+fn versus_game_with_gamepads() -> TestGame {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.mode = GameMode::Versus;
+    });
+    game.connect_gamepad(0);
+    game.connect_gamepad(1);
+    game.start();
+
+    game
+}
+
+#[test]
+fn losing_a_gamepad_in_game_pauses() {
+    let mut game = versus_game_with_gamepads();
+
+    game.disconnect_gamepad(1);
+
+    assert!(game.is_paused());
+    assert_eq!(game.lost_devices(), [1]);
+    assert_eq!(game.devices(), [pad(0), None]);
+
+    // A second lost gamepad keeps the game paused.
+    game.disconnect_gamepad(0);
+
+    assert!(game.is_paused());
+    assert_eq!(game.lost_devices(), [1, 0]);
+}
+
+#[test]
+fn reconnecting_gives_the_gamepad_back() {
+    let mut game = versus_game_with_gamepads();
+
+    game.disconnect_gamepad(1);
+    game.connect_gamepad(1);
+
+    assert_eq!(game.devices(), [pad(0), pad(1)]);
+    assert_eq!(game.lost_devices(), []);
+    // The player unpauses when ready.
+    assert!(game.is_paused());
+}
+
+#[test]
+fn losing_a_gamepad_outside_a_game_doesnt_pause() {
+    let mut game = TestGame::new();
+    game.connect_gamepad(0);
+
+    game.disconnect_gamepad(0);
+
+    assert_eq!(game.state(), GameState::StartMenu);
+    assert!(!game.is_paused());
+    assert_eq!(game.lost_devices(), []);
+}
+
+#[test]
+fn losing_the_gamepad_of_a_player_not_in_the_game_doesnt_pause() {
+    let mut game = TestGame::new();
+    game.connect_gamepad(0);
+    game.connect_gamepad(1);
+    game.start();
+
+    // Alone only player one plays.
+    game.disconnect_gamepad(1);
+
+    assert!(!game.is_paused());
+    assert_eq!(game.lost_devices(), []);
+    assert_eq!(game.devices(), [pad(0), None]);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use common::{cell, TestGame};
+use snake::libs::{
+    eatables::powerups::{powerup::Powerup, registry::PowerupRegistry},
+    game_configuration::{BoundaryMode, GameConfiguration},
+    game_states::GameState,
+    player::PlayerId,
+    sim::snake_sim::{SimFood, SnakeSim},
+};
+
+fn game_with_food(food: Powerup) -> TestGame {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = BoundaryMode::Wrap;
+    });
+    game.start();
+    game.clear_food();
+    game.place_food(cell(6, 5), food);
+
+    game
+}
+
+#[test]
+fn eating_grows_the_snake_and_scores() {
+    let mut game = game_with_food(Powerup::Normal);
+
+    game.advance_ticks(1);
+    assert_eq!(game.score(PlayerId::ONE), 1);
+
+    // The new tail spawns on the head and trails it from the next tick.
+    game.advance_ticks(1);
+    assert_eq!(
+        game.snake_cells(PlayerId::ONE),
+        vec![cell(6, 4), cell(6, 5)]
+    );
+}
+
+#[test]
+fn eaten_food_is_replaced() {
+    let mut game = game_with_food(Powerup::Normal);
+
+    game.advance_ticks(2);
+
+    let foods = game.food_cells();
+    assert_eq!(foods.len(), 1);
+    assert!(!game.snake_cells(PlayerId::ONE).contains(&foods[0]));
+}
+
+#[test]
+fn snake_keeps_growing() {
+    let mut game = game_with_food(Powerup::Normal);
+
+    for y in [4, 3, 2] {
+        game.advance_ticks(1);
+        game.clear_food();
+        game.place_food(cell(6, y), Powerup::Normal);
+    }
+    game.advance_ticks(2);
+
+    assert_eq!(game.score(PlayerId::ONE), 4);
+    assert_eq!(game.snake_cells(PlayerId::ONE).len(), 5);
+    assert_eq!(game.state(), GameState::InGame);
+}
+
+#[test]
+fn bot_frenzy_doesnt_multiply_the_player_score() {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = BoundaryMode::Wrap;
+        configuration.bots = 1;
+    });
+    game.start();
+    game.clear_food();
+
+    // The bot eats a frenzy on the first tick, the player a normal food on the second.
+    let bot = PlayerId(1);
+    let head = game.head(bot);
+    let ahead = if head.y < 6 { head.y + 1 } else { head.y - 1 };
+    game.place_food(cell(head.x, ahead), Powerup::Frenzy);
+    game.place_food(cell(6, 4), Powerup::Normal);
+    game.advance_ticks(2);
+
+    assert!(game.effects(bot).is_active(Powerup::Frenzy));
+    assert!(!game.effects(PlayerId::ONE).is_active(Powerup::Frenzy));
+    assert_eq!(game.score(PlayerId::ONE), 1);
+}
+
+#[test]
+fn food_is_replaced_on_the_tick_it_is_eaten() {
+    let mut game = game_with_food(Powerup::Normal);
+
+    // A single frame, the next one could already be a tick when replays fast-forward.
+    game.advance_fraction(1.0);
+
+    let foods = game.food_cells();
+    assert_eq!(game.score(PlayerId::ONE), 1);
+    assert_eq!(foods.len(), 1);
+    assert_ne!(foods[0], cell(6, 5));
+}
+
+#[test]
+fn food_is_placed_like_in_snake_sim() {
+    let mut game = game_with_food(Powerup::Normal);
+    let configuration = game.app.world.resource::<GameConfiguration>();
+    let registry = PowerupRegistry::default();
+    let mut sim = SnakeSim::new(configuration, &registry, common::SEED);
+
+    // Food covering the top rows, a feast must not put new food on any of it.
+    let mut foods = vec![cell(6, 5)];
+    game.clear_food();
+    game.place_food(cell(6, 5), Powerup::Feast);
+    for x in 0..sim.width {
+        for y in 0..2 {
+            game.place_food(cell(x, y), Powerup::Normal);
+            foods.push(cell(x, y));
+        }
+    }
+
+    sim.foods = foods
+        .iter()
+        .map(|pos| SimFood {
+            pos: *pos,
+            id: registry
+                .get(if *pos == cell(6, 5) {
+                    Powerup::Feast
+                } else {
+                    Powerup::Normal
+                })
+                .id,
+        })
+        .collect();
+
+    game.advance_ticks(1);
+    sim.step(None);
+
+    let mut game_foods = game.food_cells();
+    let mut sim_foods: Vec<_> = sim.foods.iter().map(|food| food.pos).collect();
+    game_foods.sort_by_key(|cell| (cell.x, cell.y));
+    sim_foods.sort_by_key(|cell| (cell.x, cell.y));
+
+    assert_eq!(game.score(PlayerId::ONE), 1);
+    assert_eq!(game_foods, sim_foods);
+    assert!(game_foods.len() > foods.len());
+}
+
+#[test]
+fn full_board_gets_no_new_food() {
+    // One column of two cells, the snake fills it after eating twice.
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = BoundaryMode::Wrap;
+        configuration.set_grid(1, 2, 40.);
+    });
+    game.start();
+    assert_eq!(game.food_cells(), vec![cell(0, 0)]);
+
+    game.advance_ticks(2);
+
+    assert_eq!(game.score(PlayerId::ONE), 2);
+    assert_eq!(game.food_cells(), vec![]);
+    assert_eq!(game.state(), GameState::InGame);
+}
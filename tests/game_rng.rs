@@ -0,0 +1,30 @@
+use rand::{Rng, RngCore};
+use snake::libs::game_rng::GameRng;
+
+#[test]
+fn same_seed_gives_the_same_numbers() {
+    let mut first = GameRng::new(7);
+    let mut second = GameRng::new(7);
+
+    for _ in 0..100 {
+        assert_eq!(first.next_u64(), second.next_u64());
+    }
+}
+
+#[test]
+fn different_seeds_give_different_numbers() {
+    let mut first = GameRng::new(1);
+    let mut second = GameRng::new(2);
+
+    assert!((0..8).any(|_| first.next_u32() != second.next_u32()));
+}
+
+#[test]
+fn clones_continue_the_same_sequence() {
+    let mut rng = GameRng::new(3);
+    rng.gen_range(0..10);
+    let mut clone = rng.clone();
+
+    assert_eq!(rng.gen_range(0..1000), clone.gen_range(0..1000));
+    assert_eq!(clone.seed(), 3);
+}
@@ -0,0 +1,116 @@
+use snake::libs::{
+    game_configuration::GameDifficulty,
+    high_scores::{
+        FileStorage, HighScoreEntry, HighScoreStorage, HighScoreTable, MemoryStorage,
+        MAX_HIGH_SCORES,
+    },
+};
+
+const DIFFICULTY: GameDifficulty = GameDifficulty::Medium;
+
+fn entry(score: u32, name: &str) -> HighScoreEntry {
+    HighScoreEntry {
+        score,
+        length: score + 1,
+        duration_secs: 12.5,
+        date: 1_700_000_000,
+        name: Some(name.to_string()),
+    }
+}
+
+fn names(table: &HighScoreTable) -> Vec<&str> {
+    table
+        .get(DIFFICULTY)
+        .iter()
+        .map(|entry| entry.name.as_deref().unwrap())
+        .collect()
+}
+
+#[test]
+fn runs_without_points_never_rank() {
+    let mut table = HighScoreTable::default();
+
+    assert_eq!(table.rank(DIFFICULTY, 0), None);
+    assert_eq!(table.insert(DIFFICULTY, entry(0, "zero")), None);
+    assert!(table.get(DIFFICULTY).is_empty());
+}
+
+#[test]
+fn runs_are_sorted_by_score() {
+    let mut table = HighScoreTable::default();
+
+    assert_eq!(table.insert(DIFFICULTY, entry(5, "five")), Some(0));
+    assert_eq!(table.insert(DIFFICULTY, entry(9, "nine")), Some(0));
+    assert_eq!(table.insert(DIFFICULTY, entry(7, "seven")), Some(1));
+
+    assert_eq!(names(&table), ["nine", "seven", "five"]);
+    assert!(table.get(GameDifficulty::Hard).is_empty());
+}
+
+#[test]
+fn ties_go_below_earlier_runs() {
+    let mut table = HighScoreTable::default();
+    table.insert(DIFFICULTY, entry(5, "first"));
+    table.insert(DIFFICULTY, entry(3, "low"));
+
+    assert_eq!(table.rank(DIFFICULTY, 5), Some(1));
+    assert_eq!(table.insert(DIFFICULTY, entry(5, "second")), Some(1));
+    assert_eq!(names(&table), ["first", "second", "low"]);
+}
+
+#[test]
+fn full_tables_drop_the_lowest_run() {
+    let mut table = HighScoreTable::default();
+
+    for score in 1..=MAX_HIGH_SCORES as u32 {
+        table.insert(DIFFICULTY, entry(score * 10, &score.to_string()));
+    }
+
+    // Lower than every run, and tying the lowest one, doesn't make it.
+    assert_eq!(table.rank(DIFFICULTY, 5), None);
+    assert_eq!(table.insert(DIFFICULTY, entry(10, "tie")), None);
+
+    assert_eq!(
+        table.insert(DIFFICULTY, entry(15, "new")),
+        Some(MAX_HIGH_SCORES - 1)
+    );
+    assert_eq!(table.get(DIFFICULTY).len(), MAX_HIGH_SCORES);
+    assert_eq!(table.get(DIFFICULTY).last().unwrap().score, 15);
+}
+
+fn filled_table() -> HighScoreTable {
+    let mut table = HighScoreTable::default();
+    table.insert(DIFFICULTY, entry(8, "eight"));
+    table.insert(GameDifficulty::Hard, entry(3, "three"));
+
+    table
+}
+
+#[test]
+fn memory_storage_keeps_the_saved_table() {
+    let storage = MemoryStorage::default();
+    assert_eq!(storage.load().unwrap(), HighScoreTable::default());
+
+    let table = filled_table();
+    storage.save(&table).unwrap();
+
+    assert_eq!(storage.load().unwrap(), table);
+}
+
+#[test]
+fn file_storage_keeps_the_saved_table() {
+    let dir = std::env::temp_dir().join(format!("snake-high-scores-{}", std::process::id()));
+    let storage = FileStorage {
+        path: dir.join("high_scores.ron"),
+    };
+
+    // Nothing is saved before the first game.
+    assert_eq!(storage.load().unwrap(), HighScoreTable::default());
+
+    let table = filled_table();
+    storage.save(&table).unwrap();
+    let loaded = storage.load().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(loaded, table);
+}
@@ -0,0 +1,103 @@
+use snake::libs::{
+    cell::Cell,
+    game_configuration::{GameConfiguration, GameMode},
+    input::direction::Direction,
+    level::{Level, LevelError, LEVEL_FILES},
+};
+
+fn configuration_with_level(text: &str, mode: GameMode) -> GameConfiguration {
+    let mut configuration = GameConfiguration {
+        mode,
+        ..Default::default()
+    };
+    configuration.set_level(Level::parse("test", text).unwrap());
+
+    configuration
+}
+
+#[test]
+fn shipped_levels_parse() {
+    for path in LEVEL_FILES {
+        let text = std::fs::read_to_string(format!("assets/{path}")).unwrap();
+
+        assert!(Level::parse(path, &text).is_ok(), "{path} doesn't parse");
+    }
+}
+
+#[test]
+fn tiles_are_read() {
+    let level = Level::parse("small", "#.*\n.>.\n...\n").unwrap();
+
+    assert_eq!((level.width, level.height), (3, 3));
+    assert_eq!(level.walls, vec![Cell { x: 0, y: 0 }]);
+    assert_eq!(level.food_spawns, vec![Cell { x: 2, y: 0 }]);
+    assert_eq!(level.start, Cell { x: 1, y: 1 });
+    assert_eq!(level.start_direction, Direction::Right);
+}
+
+#[test]
+fn empty_levels_are_rejected() {
+    assert!(matches!(
+        Level::parse("empty", "\n\n"),
+        Err(LevelError::Empty)
+    ));
+}
+
+#[test]
+fn uneven_rows_are_rejected() {
+    assert!(matches!(
+        Level::parse("uneven", "...\n..\n..."),
+        Err(LevelError::UnevenRows(1))
+    ));
+}
+
+#[test]
+fn unknown_tiles_are_rejected() {
+    assert!(matches!(
+        Level::parse("unknown", "...\n.x.\n..."),
+        Err(LevelError::UnknownTile('x', Cell { x: 1, y: 1 }))
+    ));
+}
+
+#[test]
+fn several_starts_are_rejected() {
+    assert!(matches!(
+        Level::parse("starts", "^..\n...\n..v"),
+        Err(LevelError::SeveralStarts)
+    ));
+}
+
+#[test]
+fn start_moves_off_a_wall() {
+    // No start marker, the middle of the board is a wall.
+    let configuration =
+        configuration_with_level(".....\n.....\n..#..\n.....\n.....", GameMode::Single);
+
+    let (start, _) = configuration.start_position();
+
+    assert_ne!(start, Cell { x: 2, y: 2 });
+    assert!(configuration.field.contains(&start));
+}
+
+#[test]
+fn mirrored_start_moves_off_a_wall() {
+    let configuration =
+        configuration_with_level(".....\n.^...\n.....\n...#.\n.....", GameMode::Versus);
+
+    let starts = configuration.start_positions();
+
+    assert_eq!(starts[0].0, Cell { x: 1, y: 1 });
+    assert_ne!(starts[1].0, Cell { x: 3, y: 3 });
+    assert_ne!(starts[1].0, starts[0].0);
+    assert!(configuration.field.contains(&starts[1].0));
+}
+
+#[test]
+fn mirrored_start_moves_off_the_first_snake() {
+    // The first start sits in the middle, so the mirrored cell is the same one.
+    let configuration = configuration_with_level("...\n.^.\n...", GameMode::Versus);
+
+    let starts = configuration.start_positions();
+
+    assert_ne!(starts[1].0, starts[0].0);
+}
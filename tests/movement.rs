@@ -0,0 +1,90 @@
+mod common;
+
+use common::{cell, TestGame};
+use snake::libs::{
+    death::DeathCause, game_configuration::BoundaryMode, game_states::GameState,
+    input::direction::Direction, player::PlayerId,
+};
+
+fn solo_game(boundary_mode: BoundaryMode) -> TestGame {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = boundary_mode;
+    });
+    game.start();
+    game.clear_food();
+
+    game
+}
+
+#[test]
+fn snake_starts_in_the_middle() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    assert_eq!(game.state(), GameState::InGame);
+    assert_eq!(game.snake_cells(PlayerId::ONE), vec![cell(6, 6)]);
+    assert_eq!(game.score(PlayerId::ONE), 0);
+}
+
+#[test]
+fn snake_moves_one_cell_per_tick() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    game.advance_ticks(3);
+
+    assert_eq!(game.tick(), 3);
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 3));
+}
+
+#[test]
+fn snake_stays_put_between_ticks() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    game.app.update();
+    game.app.update();
+
+    assert_eq!(game.tick(), 0);
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 6));
+}
+
+#[test]
+fn move_event_turns_the_snake() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    game.send_move(PlayerId::ONE, Direction::Left);
+    game.advance_ticks(2);
+
+    assert_eq!(game.head(PlayerId::ONE), cell(4, 6));
+}
+
+#[test]
+fn reversing_is_ignored() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    game.send_move(PlayerId::ONE, Direction::Down);
+    game.advance_ticks(1);
+
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 5));
+}
+
+#[test]
+fn solid_border_ends_the_game() {
+    let mut game = solo_game(BoundaryMode::Solid);
+
+    game.advance_ticks(6);
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 0));
+    assert_eq!(game.state(), GameState::InGame);
+
+    game.advance_ticks(1);
+    assert_eq!(game.state(), GameState::FinishMenu);
+    assert_eq!(game.last_death_cause(), DeathCause::Wall);
+}
+
+#[test]
+fn wrapping_border_moves_the_snake_to_the_other_side() {
+    let mut game = solo_game(BoundaryMode::Wrap);
+
+    game.advance_ticks(7);
+
+    assert_eq!(game.state(), GameState::InGame);
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 12));
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{cell, TestGame};
+use snake::libs::{
+    death::DeathCause,
+    eatables::powerups::{
+        powerup::Powerup,
+        registry::{PowerupRegistry, PowerupRegistryError},
+    },
+    game_configuration::BoundaryMode,
+    game_states::GameState,
+    input::direction::Direction,
+    player::PlayerId,
+};
+
+#[test]
+fn shipped_powerups_file_is_valid() {
+    let registry = PowerupRegistry::from_ron(include_str!("../assets/powerups.ron")).unwrap();
+
+    for powerup in Powerup::ALL {
+        assert_eq!(registry.get(powerup).effect, powerup);
+    }
+}
+
+#[test]
+fn entries_may_share_an_effect() {
+    let registry = PowerupRegistry::from_ron(
+        r#"(powerups: [
+            (id: "mild", effect: Slowdown, color: (0.0, 0.0, 0.9), weight: 1.0, duration: 10, magnitude: 1.5),
+            (id: "strong", effect: Slowdown, color: (0.0, 0.0, 0.5), weight: 1.0, duration: 10, magnitude: 3.0),
+        ])"#,
+    )
+    .unwrap();
+
+    assert_eq!(registry.entry("mild").magnitude, 1.5);
+    assert_eq!(registry.entry("strong").magnitude, 3.0);
+    assert_eq!(registry.get(Powerup::Slowdown).id, "mild");
+}
+
+#[test]
+fn unknown_ids_fall_back() {
+    let registry = PowerupRegistry::from_ron(
+        r#"(powerups: [
+            (id: "big", effect: Feast, color: (0.0, 0.9, 0.0), weight: 1.0, duration: 0, magnitude: 8.0),
+        ])"#,
+    )
+    .unwrap();
+
+    assert_eq!(registry.entry("ghost").effect, Powerup::Ghost);
+    assert_eq!(registry.entry("missing").effect, Powerup::Normal);
+}
+
+#[test]
+fn duplicate_ids_are_rejected() {
+    let result = PowerupRegistry::from_ron(
+        r#"(powerups: [
+            (id: "food", effect: Normal, color: (0.9, 0.1, 0.1), weight: 1.0, duration: 0, magnitude: 0.0),
+            (id: "food", effect: Feast, color: (0.0, 0.9, 0.0), weight: 1.0, duration: 0, magnitude: 4.0),
+        ])"#,
+    );
+
+    assert!(matches!(result, Err(PowerupRegistryError::DuplicateId(id)) if id == "food"));
+}
+
+#[test]
+fn negative_weights_are_rejected() {
+    let result = PowerupRegistry::from_ron(
+        r#"(powerups: [
+            (id: "normal", effect: Normal, color: (0.9, 0.1, 0.1), weight: -1.0, duration: 0, magnitude: 0.0),
+        ])"#,
+    );
+
+    assert!(matches!(result, Err(PowerupRegistryError::NegativeWeight(id)) if id == "normal"));
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let result = PowerupRegistry::from_ron("(powerups: [(id: \"normal\")])");
+
+    assert!(matches!(result, Err(PowerupRegistryError::Format(_))));
+}
+
+#[test]
+fn ghost_passes_through_its_own_body_until_it_ends() {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = BoundaryMode::Wrap;
+    });
+    game.start();
+    game.clear_food();
+
+    // Grows to six parts going up, the last food is a ghost.
+    for y in 2..6 {
+        game.place_food(cell(6, y), Powerup::Normal);
+    }
+    game.place_food(cell(6, 1), Powerup::Ghost);
+    game.advance_ticks(5);
+    game.clear_food();
+
+    assert!(game.effects(PlayerId::ONE).is_active(Powerup::Ghost));
+
+    // Circling on four cells runs into the body from the third move on.
+    let circle = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ];
+    let mut moves = 0;
+
+    while game.state() == GameState::InGame && moves < 40 {
+        let ghost = game.effects(PlayerId::ONE).remaining_ticks(Powerup::Ghost);
+
+        game.send_move(PlayerId::ONE, circle[moves % 4]);
+        game.advance_ticks(1);
+        moves += 1;
+
+        if moves >= 3 && game.state() == GameState::InGame {
+            let head = game.head(PlayerId::ONE);
+            assert!(game.snake_cells(PlayerId::ONE)[1..].contains(&head));
+            assert!(ghost > 0);
+        }
+    }
+
+    assert_eq!(game.state(), GameState::FinishMenu);
+    assert_eq!(game.last_death_cause(), DeathCause::SelfCollision);
+    assert_eq!(
+        moves as u32,
+        PowerupRegistry::default().get(Powerup::Ghost).duration
+    );
+}
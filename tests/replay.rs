@@ -0,0 +1,72 @@
+use snake::libs::{
+    eatables::powerups::{powerup::Powerup, registry::PowerupRegistry},
+    game_configuration::{BoundaryMode, GameConfiguration, GameMode},
+    input::direction::Direction,
+    player::PlayerId,
+    replay::replay_file::{Replay, ReplayAction, ReplayEntry, ReplayError, REPLAY_VERSION},
+};
+
+fn recorded_replay() -> Replay {
+    let configuration = GameConfiguration {
+        boundary_mode: BoundaryMode::Solid,
+        mode: GameMode::Versus,
+        ..Default::default()
+    };
+
+    let mut registry = PowerupRegistry::default();
+    registry.powerups[0].weight = 12.5;
+
+    let mut replay = Replay::new(99, &configuration, &registry);
+    replay.entries = vec![
+        ReplayEntry {
+            tick: 3,
+            action: ReplayAction::Move(PlayerId::TWO, Direction::Left),
+        },
+        ReplayEntry {
+            tick: 8,
+            action: ReplayAction::Pause,
+        },
+    ];
+
+    replay
+}
+
+#[test]
+fn replays_survive_a_round_trip() {
+    let replay = recorded_replay();
+
+    let loaded = Replay::from_ron(&replay.to_ron().unwrap()).unwrap();
+
+    assert_eq!(loaded, replay);
+    assert_eq!(loaded.powerups.get(Powerup::Normal).weight, 12.5);
+}
+
+#[test]
+fn replays_restore_the_configuration() {
+    let replay = recorded_replay();
+    let mut configuration = GameConfiguration::default();
+
+    replay.apply_to(&mut configuration);
+
+    assert_eq!(configuration.seed, Some(99));
+    assert_eq!(configuration.boundary_mode, BoundaryMode::Solid);
+    assert_eq!(configuration.mode, GameMode::Versus);
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let mut replay = recorded_replay();
+    replay.version = REPLAY_VERSION + 1;
+
+    let result = Replay::from_ron(&replay.to_ron().unwrap());
+
+    assert!(matches!(result, Err(ReplayError::Version(version)) if version == REPLAY_VERSION + 1));
+}
+
+#[test]
+fn malformed_replays_are_rejected() {
+    assert!(matches!(
+        Replay::from_ron("(version: 1"),
+        Err(ReplayError::Format(_))
+    ));
+}
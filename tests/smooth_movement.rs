@@ -0,0 +1,81 @@
+mod common;
+
+use common::{cell, TestGame};
+use snake::libs::{
+    game_configuration::{BoundaryMode, GameConfiguration},
+    input::direction::Direction,
+    player::PlayerId,
+    utils::grid_to_screen,
+};
+
+fn smooth_game(smooth_movement: bool) -> TestGame {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.boundary_mode = BoundaryMode::Wrap;
+        configuration.smooth_movement = smooth_movement;
+    });
+    game.start();
+    game.clear_food();
+
+    game
+}
+
+fn screen(game: &TestGame, x: u32, y: u32) -> (f32, f32) {
+    let configuration = game.app.world.resource::<GameConfiguration>();
+    let pos = grid_to_screen(x, y, configuration);
+
+    (pos.x, pos.y)
+}
+
+#[test]
+fn smooth_movement_keeps_the_grid() {
+    let mut snapping = smooth_game(false);
+    let mut smooth = smooth_game(true);
+
+    for game in [&mut snapping, &mut smooth] {
+        game.advance_ticks(2);
+        game.send_move(PlayerId::ONE, Direction::Left);
+        game.advance_ticks(8);
+    }
+
+    assert_eq!(smooth.head(PlayerId::ONE), cell(11, 4));
+    assert_eq!(
+        snapping.snake_cells(PlayerId::ONE),
+        smooth.snake_cells(PlayerId::ONE)
+    );
+}
+
+#[test]
+fn head_slides_between_cells() {
+    let mut game = smooth_game(true);
+
+    game.advance_ticks(1);
+    game.advance_fraction(0.5);
+
+    // Halfway from (6, 6) to (6, 5).
+    let (x, from_y) = screen(&game, 6, 6);
+    let (_, to_y) = screen(&game, 6, 5);
+    let translation = game.head_translation(PlayerId::ONE);
+
+    assert_eq!(translation.x, x);
+    assert!((translation.y - (from_y + to_y) / 2.).abs() < 1.);
+}
+
+#[test]
+fn wrapping_head_does_not_cross_the_board() {
+    let mut game = smooth_game(true);
+
+    game.advance_ticks(7);
+    assert_eq!(game.head(PlayerId::ONE), cell(6, 12));
+
+    // Leaving the top edge...
+    let (_, top) = screen(&game, 6, 0);
+    assert!(game.head_translation(PlayerId::ONE).y >= top - 1.);
+
+    // ...and coming in from below the bottom one.
+    game.advance_fraction(0.75);
+    let (_, bottom) = screen(&game, 6, 12);
+    let cell_size = game.app.world.resource::<GameConfiguration>().cell_size;
+    let y = game.head_translation(PlayerId::ONE).y;
+
+    assert!(y < bottom && y > bottom - cell_size);
+}
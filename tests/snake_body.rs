@@ -0,0 +1,73 @@
+use snake::libs::{
+    cell::Cell,
+    input::direction::Direction::{self, Down, Left, Right, Up},
+    sim::rules::neighbour_direction,
+    snake_body::{body_piece, BodyPiece, BodyPieceKind},
+};
+
+fn piece(kind: BodyPieceKind, rotation: Direction) -> Option<BodyPiece> {
+    Some(BodyPiece { kind, rotation })
+}
+
+#[test]
+fn straight_pieces() {
+    assert_eq!(
+        body_piece(Some(Up), Some(Down)),
+        piece(BodyPieceKind::Straight, Up)
+    );
+    assert_eq!(
+        body_piece(Some(Left), Some(Right)),
+        piece(BodyPieceKind::Straight, Left)
+    );
+}
+
+#[test]
+fn corner_pieces_join_both_neighbours() {
+    // The unrotated corner joins up and right, whichever side the head is on.
+    assert_eq!(
+        body_piece(Some(Up), Some(Right)),
+        piece(BodyPieceKind::Corner, Up)
+    );
+    assert_eq!(
+        body_piece(Some(Right), Some(Up)),
+        piece(BodyPieceKind::Corner, Up)
+    );
+    assert_eq!(
+        body_piece(Some(Right), Some(Down)),
+        piece(BodyPieceKind::Corner, Right)
+    );
+    assert_eq!(
+        body_piece(Some(Left), Some(Down)),
+        piece(BodyPieceKind::Corner, Down)
+    );
+    assert_eq!(
+        body_piece(Some(Up), Some(Left)),
+        piece(BodyPieceKind::Corner, Left)
+    );
+}
+
+#[test]
+fn tail_end_points_at_the_body() {
+    assert_eq!(
+        body_piece(Some(Left), None),
+        piece(BodyPieceKind::TailEnd, Left)
+    );
+    assert_eq!(body_piece(None, None), None);
+}
+
+#[test]
+fn neighbours_across_a_wrapping_edge() {
+    let cell = |x, y| Cell { x, y };
+
+    assert_eq!(neighbour_direction(cell(3, 3), cell(3, 2), 9, 9), Some(Up));
+    assert_eq!(
+        neighbour_direction(cell(0, 4), cell(8, 4), 9, 9),
+        Some(Left)
+    );
+    assert_eq!(
+        neighbour_direction(cell(4, 8), cell(4, 0), 9, 9),
+        Some(Down)
+    );
+    assert_eq!(neighbour_direction(cell(3, 3), cell(5, 3), 9, 9), None);
+    assert_eq!(neighbour_direction(cell(3, 3), cell(3, 3), 9, 9), None);
+}
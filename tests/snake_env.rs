@@ -0,0 +1,123 @@
+use snake::libs::{
+    death::DeathCause,
+    eatables::powerups::{powerup::Powerup, registry::PowerupRegistry},
+    game_configuration::{BoundaryMode, GameConfiguration},
+    input::direction::Direction,
+    sim::snake_env::{food_channel, RewardConfig, SnakeEnv, BODY_CHANNEL, CHANNELS, HEAD_CHANNEL},
+};
+
+fn new_env(boundary_mode: BoundaryMode, rewards: RewardConfig) -> SnakeEnv {
+    let configuration = GameConfiguration {
+        boundary_mode,
+        ..Default::default()
+    };
+
+    SnakeEnv::new(configuration, PowerupRegistry::default(), rewards)
+}
+
+#[test]
+fn reset_shows_the_new_board() {
+    let mut env = new_env(BoundaryMode::Solid, RewardConfig::default());
+
+    let observation = env.reset(3);
+    let sim = env.sim();
+
+    assert_eq!(
+        observation.grid.len(),
+        CHANNELS * (sim.width * sim.height) as usize
+    );
+    assert_eq!(observation.get(HEAD_CHANNEL, sim.head()), 1.);
+    assert_eq!(
+        observation.grid.iter().filter(|value| **value > 0.).count(),
+        2
+    );
+    assert_eq!(
+        observation.get(food_channel(Powerup::Normal), sim.foods[0].pos),
+        1.
+    );
+    assert_eq!(observation, env.reset(3));
+}
+
+#[test]
+fn step_moves_the_head_in_the_observation() {
+    let mut env = new_env(BoundaryMode::Solid, RewardConfig::default());
+    env.reset(3);
+    let head = env.sim().head();
+
+    let (observation, _, done, info) = env.step(Some(Direction::Left));
+
+    assert!(!done);
+    assert_eq!(info.tick, 1);
+    assert_eq!(observation.direction, Direction::Left);
+    assert_eq!(observation.get(HEAD_CHANNEL, head), 0.);
+    assert_eq!(observation.get(HEAD_CHANNEL, env.sim().head()), 1.);
+    assert_eq!(observation.get(BODY_CHANNEL, head), 0.);
+}
+
+#[test]
+fn dying_ends_the_episode_with_the_death_reward() {
+    let rewards = RewardConfig {
+        food: 0.,
+        death: -5.,
+        step: -0.1,
+        approach: 0.,
+    };
+    let mut env = new_env(BoundaryMode::Solid, rewards);
+    env.reset(1);
+
+    let mut last = None;
+    for _ in 0..=env.sim().height {
+        let (_, reward, done, info) = env.step(Some(Direction::Up));
+        last = Some((reward, done, info));
+
+        if done {
+            break;
+        }
+    }
+
+    let (reward, done, info) = last.unwrap();
+    assert!(done);
+    assert!(!info.truncated);
+    assert_eq!(info.death, Some(DeathCause::Wall));
+    assert_eq!(reward, -5.1);
+
+    // A finished episode stays finished.
+    let (_, _, done, info) = env.step(None);
+    assert!(done);
+    assert_eq!(info.tick, env.sim().tick);
+}
+
+#[test]
+fn episodes_are_truncated_after_max_ticks() {
+    let mut env = new_env(BoundaryMode::Wrap, RewardConfig::default());
+    env.max_ticks = Some(3);
+    env.reset(1);
+
+    let done: Vec<bool> = (0..3).map(|_| env.step(None).2).collect();
+
+    assert_eq!(done, [false, false, true]);
+    assert!(env.step(None).3.truncated);
+}
+
+#[test]
+fn approach_reward_counts_steps_across_wrapping_edges() {
+    let rewards = RewardConfig {
+        food: 0.,
+        death: 0.,
+        step: 0.,
+        approach: 1.,
+    };
+    let mut env = new_env(BoundaryMode::Wrap, rewards);
+    env.reset(1);
+
+    // Going once around the board crosses the edge, every step is still one cell.
+    for _ in 0..env.sim().height {
+        let foods = env.sim().foods.clone();
+        let (_, reward, done, _) = env.step(Some(Direction::Up));
+
+        assert!(!done);
+        if env.sim().foods == foods {
+            assert!(reward.abs() <= 1., "{reward}");
+        }
+    }
+}
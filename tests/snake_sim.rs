@@ -0,0 +1,219 @@
+use snake::libs::{
+    cell::Cell,
+    death::DeathCause,
+    eatables::powerups::{
+        powerup::Powerup,
+        registry::{PowerupEntry, PowerupRegistry},
+    },
+    game_configuration::{BoundaryMode, GameConfiguration},
+    game_rng::GameRng,
+    input::direction::Direction,
+    sim::{
+        bot::{choose_direction, BotLevel, BotView},
+        rules,
+        sim_event::SimEvent,
+        snake_sim::{SimFood, SnakeSim},
+    },
+};
+
+fn new_sim(seed: u64) -> SnakeSim {
+    SnakeSim::new(
+        &GameConfiguration::default(),
+        &PowerupRegistry::default(),
+        seed,
+    )
+}
+
+// Lets a bot play `ticks` ticks and returns every event.
+fn play(sim: &mut SnakeSim, ticks: u64) -> Vec<SimEvent> {
+    let mut rng = GameRng::new(sim.seed());
+    let mut events = Vec::new();
+
+    while !sim.game_over && sim.tick < ticks {
+        let view = BotView::from_sim(sim);
+        let direction =
+            choose_direction(BotLevel::Greedy, &view, sim.head(), sim.direction, &mut rng);
+
+        events.extend(sim.step(Some(direction)));
+    }
+
+    events
+}
+
+fn food(sim: &SnakeSim, pos: Cell, powerup: Powerup) -> SimFood {
+    SimFood {
+        pos,
+        id: sim.registry.get(powerup).id,
+    }
+}
+
+#[test]
+fn same_seed_and_inputs_play_the_same_game() {
+    let mut first = new_sim(11);
+    let mut second = new_sim(11);
+
+    let first_events = play(&mut first, 300);
+    let second_events = play(&mut second, 300);
+
+    assert_eq!(first_events, second_events);
+    assert_eq!(first.body, second.body);
+    assert_eq!(first.foods, second.foods);
+    assert_eq!(first.score, second.score);
+    assert!(first.score > 0);
+}
+
+#[test]
+fn seed_changes_the_food() {
+    let foods: Vec<Vec<SimFood>> = (0..4).map(|seed| new_sim(seed).foods).collect();
+
+    assert!(foods.iter().any(|other| *other != foods[0]));
+}
+
+#[test]
+fn eating_grows_and_replaces_the_food() {
+    let mut sim = new_sim(1);
+    let ahead = Cell {
+        x: sim.head().x,
+        y: sim.head().y - 1,
+    };
+    sim.foods = vec![food(&sim, ahead, Powerup::Normal)];
+
+    let events = sim.step(None);
+
+    assert!(events.contains(&SimEvent::Grew));
+    assert_eq!(sim.score, 1);
+    assert_eq!(sim.body.len(), 2);
+    assert_eq!(sim.foods.len(), 1);
+    assert_ne!(sim.foods[0].pos, ahead);
+}
+
+#[test]
+fn reversing_is_ignored() {
+    let mut sim = new_sim(1);
+    sim.foods.clear();
+    let head = sim.head();
+
+    sim.step(Some(Direction::Down));
+
+    assert_eq!(sim.direction, Direction::Up);
+    assert_eq!(sim.head().y, head.y - 1);
+}
+
+// Runs straight up until the top border, the snake starts in the middle of the board.
+fn run_into_top_border(boundary_mode: BoundaryMode) -> (SnakeSim, Vec<SimEvent>) {
+    let configuration = GameConfiguration {
+        boundary_mode,
+        ..Default::default()
+    };
+    let mut sim = SnakeSim::new(&configuration, &PowerupRegistry::default(), 1);
+    sim.foods.clear();
+
+    let mut events = Vec::new();
+    for _ in 0..=sim.head().y {
+        events.extend(sim.step(None));
+    }
+
+    (sim, events)
+}
+
+#[test]
+fn solid_border_ends_the_game() {
+    let (sim, events) = run_into_top_border(BoundaryMode::Solid);
+
+    assert!(sim.game_over);
+    assert_eq!(events.last(), Some(&SimEvent::Died(DeathCause::Wall)));
+    assert_eq!(sim.head().y, 0);
+}
+
+#[test]
+fn wrapping_border_moves_the_snake_to_the_other_side() {
+    let (sim, _) = run_into_top_border(BoundaryMode::Wrap);
+
+    assert!(!sim.game_over);
+    assert_eq!(sim.head().y, sim.height - 1);
+}
+
+#[test]
+fn move_cell_follows_the_boundary() {
+    let corner = Cell { x: 0, y: 0 };
+
+    assert_eq!(
+        rules::move_cell(corner, Direction::Left, 5, 4, BoundaryMode::Solid),
+        None
+    );
+    assert_eq!(
+        rules::move_cell(corner, Direction::Left, 5, 4, BoundaryMode::Wrap),
+        Some(Cell { x: 4, y: 0 })
+    );
+    assert_eq!(
+        rules::move_cell(corner, Direction::Down, 5, 4, BoundaryMode::Solid),
+        Some(Cell { x: 0, y: 1 })
+    );
+}
+
+// A snake of five parts about to turn into itself, every move from the third one on
+// runs into its own body. `ghost_ticks` of Ghost are running.
+fn circling_sim(ghost_ticks: u32) -> SnakeSim {
+    let mut sim = new_sim(1);
+    sim.foods.clear();
+    sim.body = (6..11).map(|y| Cell { x: 6, y }).collect();
+    sim.direction = Direction::Up;
+
+    if ghost_ticks > 0 {
+        let ghost = PowerupEntry {
+            duration: ghost_ticks,
+            ..sim.registry.get(Powerup::Ghost)
+        };
+        sim.effects.start(&ghost);
+    }
+
+    sim
+}
+
+const CIRCLE: [Direction; 4] = [
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+    Direction::Up,
+];
+
+#[test]
+fn running_into_itself_ends_the_game() {
+    let mut sim = circling_sim(0);
+
+    let events: Vec<SimEvent> = CIRCLE[..3]
+        .iter()
+        .flat_map(|direction| sim.step(Some(*direction)))
+        .collect();
+
+    assert!(sim.game_over);
+    assert_eq!(
+        events.last(),
+        Some(&SimEvent::Died(DeathCause::SelfCollision))
+    );
+}
+
+#[test]
+fn ghost_passes_through_itself_until_it_ends() {
+    let mut sim = circling_sim(4);
+
+    for direction in &CIRCLE[..3] {
+        sim.step(Some(*direction));
+    }
+    assert!(!sim.game_over);
+    assert!(rules::hits_tail(sim.head(), sim.body[1..].iter()));
+
+    let events = sim.step(Some(CIRCLE[3]));
+
+    assert!(sim.game_over);
+    assert_eq!(
+        events,
+        [
+            SimEvent::EffectEnded(Powerup::Ghost),
+            SimEvent::Moved {
+                head: Cell { x: 6, y: 6 }
+            },
+            SimEvent::Died(DeathCause::SelfCollision),
+        ]
+    );
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::Vec2;
+use snake::libs::input::{bindings::StickSettings, direction::Direction, stick::stick_direction};
+
+fn settings() -> StickSettings {
+    StickSettings {
+        deadzone: 0.5,
+        release: 0.3,
+    }
+}
+
+#[test]
+fn pushes_inside_the_deadzone_are_ignored() {
+    assert_eq!(
+        stick_direction(None, Vec2::new(0.4, 0.0), &settings()),
+        None
+    );
+    assert_eq!(stick_direction(None, Vec2::ZERO, &settings()), None);
+}
+
+#[test]
+fn pushes_past_the_deadzone_pick_the_dominant_direction() {
+    let settings = settings();
+
+    assert_eq!(
+        stick_direction(None, Vec2::new(0.6, 0.2), &settings),
+        Some(Direction::Right)
+    );
+    assert_eq!(
+        stick_direction(None, Vec2::new(-0.3, 0.8), &settings),
+        Some(Direction::Up)
+    );
+    assert_eq!(
+        stick_direction(None, Vec2::new(0.1, -0.9), &settings),
+        Some(Direction::Down)
+    );
+}
+
+#[test]
+fn held_direction_is_kept_until_the_release_threshold() {
+    let settings = settings();
+    let held = Some(Direction::Left);
+
+    // Between the release threshold and the deadzone the stick stays held.
+    assert_eq!(stick_direction(held, Vec2::new(-0.4, 0.0), &settings), held);
+    assert_eq!(stick_direction(held, Vec2::new(-0.2, 0.0), &settings), None);
+}
+
+#[test]
+fn a_new_direction_past_the_deadzone_takes_over() {
+    assert_eq!(
+        stick_direction(Some(Direction::Left), Vec2::new(-0.35, 0.7), &settings()),
+        Some(Direction::Up)
+    );
+}
@@ -0,0 +1,81 @@
+use snake::libs::ui::text_input::{TextInput, ARCADE_LETTERS};
+
+fn typed(text: &str, max_len: usize) -> TextInput {
+    let mut input = TextInput::new(max_len);
+
+    for letter in text.chars() {
+        input.push(letter);
+    }
+
+    input
+}
+
+#[test]
+fn typing_stops_at_max_len() {
+    let input = typed("snakes", 3);
+
+    assert_eq!(input.value, "sna");
+    // The cursor stays on the last letter, there is no room for a new one.
+    assert_eq!(input.cursor(), 2);
+    assert_eq!(input.display(), "sn[a]");
+}
+
+#[test]
+fn deleting_moves_the_cursor_to_the_end() {
+    let mut input = typed("abc", 3);
+
+    input.pop();
+    assert_eq!(input.value, "ab");
+    assert_eq!(input.cursor(), 2);
+    assert_eq!(input.display(), "ab_");
+
+    input.pop();
+    input.pop();
+    input.pop();
+    assert_eq!(input.value, "");
+    assert_eq!(input.cursor(), 0);
+}
+
+#[test]
+fn cursor_stays_inside_the_text() {
+    let mut input = typed("ab", 5);
+
+    input.move_cursor(-10);
+    assert_eq!(input.cursor(), 0);
+
+    // One past the last letter starts a new one.
+    input.move_cursor(10);
+    assert_eq!(input.cursor(), 2);
+
+    let mut full = typed("abc", 3);
+    full.move_cursor(1);
+    assert_eq!(full.cursor(), 2);
+}
+
+#[test]
+fn cycling_wraps_around_the_letters() {
+    let mut input = TextInput::new(3);
+
+    // A new letter starts on the last one, the next one is the first.
+    input.cycle_letter(1);
+    assert_eq!(input.value, "A");
+
+    input.cycle_letter(-1);
+    assert_eq!(
+        input.value,
+        ARCADE_LETTERS[ARCADE_LETTERS.len() - 1].to_string()
+    );
+
+    input.cycle_letter(ARCADE_LETTERS.len() as isize + 2);
+    assert_eq!(input.value, "B");
+}
+
+#[test]
+fn cycling_starts_from_a_typed_letter() {
+    let mut input = typed("az", 3);
+
+    input.move_cursor(-1);
+    input.cycle_letter(1);
+
+    assert_eq!(input.value, "a0");
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::{cell, TestGame};
+use snake::libs::{
+    death::DeathCause,
+    eatables::powerups::{powerup::Powerup, registry::PowerupRegistry},
+    game_configuration::{BoundaryMode, GameMode},
+    game_states::GameState,
+    input::direction::Direction,
+    player::PlayerId,
+};
+
+fn versus_game() -> TestGame {
+    let mut game = TestGame::with_configuration(|configuration| {
+        configuration.mode = GameMode::Versus;
+        configuration.boundary_mode = BoundaryMode::Solid;
+    });
+    game.start();
+    game.clear_food();
+
+    game
+}
+
+#[test]
+fn snakes_start_mirrored() {
+    let mut game = versus_game();
+
+    assert_eq!(game.head(PlayerId::ONE), cell(4, 6));
+    assert_eq!(game.head(PlayerId::TWO), cell(8, 6));
+}
+
+#[test]
+fn head_on_collision_is_a_draw() {
+    let mut game = versus_game();
+
+    game.send_move(PlayerId::ONE, Direction::Right);
+    game.send_move(PlayerId::TWO, Direction::Left);
+    game.advance_ticks(2);
+
+    assert_eq!(game.state(), GameState::FinishMenu);
+    assert_eq!(game.last_death_cause(), DeathCause::HeadOn);
+    assert_eq!(game.winner(), None);
+}
+
+#[test]
+fn surviving_player_wins() {
+    let mut game = versus_game();
+
+    // Player one hits the top border on the 7th tick, player two still has room to the left.
+    game.send_move(PlayerId::TWO, Direction::Left);
+    game.advance_ticks(7);
+
+    assert_eq!(game.state(), GameState::FinishMenu);
+    assert_eq!(game.last_death_cause(), DeathCause::Wall);
+    assert_eq!(game.winner(), Some(PlayerId::TWO));
+}
+
+#[test]
+fn ghost_only_protects_the_snake_that_ate_it() {
+    let mut game = versus_game();
+
+    // Player two grows down the middle and picks up a ghost at the end.
+    for y in 7..11 {
+        game.place_food(cell(8, y), Powerup::Normal);
+    }
+    game.place_food(cell(8, 11), Powerup::Ghost);
+
+    // Player one turns into player two's body on the 6th tick.
+    game.send_move(PlayerId::ONE, Direction::Right);
+    game.advance_ticks(1);
+    game.send_move(PlayerId::ONE, Direction::Down);
+    game.advance_ticks(2);
+    game.send_move(PlayerId::ONE, Direction::Right);
+    game.advance_ticks(2);
+
+    assert!(game.effects(PlayerId::TWO).is_active(Powerup::Ghost));
+    assert!(!game.effects(PlayerId::ONE).is_active(Powerup::Ghost));
+
+    game.advance_ticks(1);
+
+    assert_eq!(game.state(), GameState::FinishMenu);
+    assert_eq!(game.last_death_cause(), DeathCause::SnakeCollision);
+    assert_eq!(game.winner(), Some(PlayerId::TWO));
+}
+
+// Foods left on the board, none of them under a snake or on top of each other.
+fn assert_foods_apart(game: &mut TestGame, count: usize) {
+    let mut foods = game.food_cells();
+    let snakes = [
+        game.snake_cells(PlayerId::ONE),
+        game.snake_cells(PlayerId::TWO),
+    ];
+
+    assert_eq!(foods.len(), count);
+    assert!(foods
+        .iter()
+        .all(|food| !snakes.iter().flatten().any(|part| part == food)));
+
+    foods.sort_by_key(|food| (food.x, food.y));
+    foods.dedup();
+    assert_eq!(foods.len(), count);
+}
+
+#[test]
+fn both_snakes_eating_on_one_tick_get_new_food() {
+    let mut game = versus_game();
+
+    game.place_food(cell(4, 5), Powerup::Normal);
+    game.place_food(cell(8, 7), Powerup::Normal);
+    game.advance_ticks(1);
+
+    assert_eq!(game.score(PlayerId::ONE), 1);
+    assert_eq!(game.score(PlayerId::TWO), 1);
+    assert_foods_apart(&mut game, 2);
+}
+
+#[test]
+fn both_snakes_feasting_on_one_tick_get_every_food() {
+    let mut game = versus_game();
+    let feast = PowerupRegistry::default().get(Powerup::Feast).magnitude as usize;
+
+    game.place_food(cell(4, 5), Powerup::Feast);
+    game.place_food(cell(8, 7), Powerup::Feast);
+    game.advance_ticks(1);
+
+    assert_foods_apart(&mut game, 2 * feast);
+}